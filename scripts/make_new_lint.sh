@@ -33,6 +33,7 @@ SemverQuery(
     human_readable_name: "TODO",
     description: "TODO",
     required_update: Major,  // TODO
+    group: Types,  // TODO: Functions, Traits, Types or Impls
     reference_link: None,  // TODO
     query: r#"
     {
@@ -44,6 +45,7 @@ SemverQuery(
         // TODO
     },
     error_message: "TODO",
+    suggested_fix: None,  // TODO
     per_result_error_template: Some("TODO"),
 )
 EOF
@@ -1,20 +1,30 @@
-use std::{collections::BTreeMap, env, io::Write, iter::Peekable, sync::Arc, time::Duration};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::Write,
+    iter::Peekable,
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::Context;
-use clap::crate_version;
-use itertools::Itertools;
 use termcolor::Color;
 use termcolor_output::{colored, colored_ln};
 use trustfall::{FieldValue, TransparentValue};
 use trustfall_rustdoc::{VersionedCrate, VersionedIndexedCrate, VersionedRustdocAdapter};
 
 use crate::{
-    query::{ActualSemverUpdate, RequiredSemverUpdate, SemverQuery},
-    CrateReport, GlobalConfig, ReleaseType,
+    diff::BrokenDocLink,
+    query::{ActualSemverUpdate, LintGroup, LintLevel, RequiredSemverUpdate, SemverQuery},
+    reporter::{Reporter, Violation},
+    CheckError, CrateReport, GlobalConfig, ReleaseType,
 };
 
 type QueryResultItem = BTreeMap<Arc<str>, FieldValue>;
 
+/// Items whose doc comment contains this marker are not semver-checked,
+/// nor is anything inside them, like methods or fields.
+pub(crate) const SEMVER_EXEMPT_MARKER: &str = "cargo:semver-exempt";
+
 struct QueryWithResults<'a> {
     name: &'a str,
     results: Peekable<Box<dyn Iterator<Item = QueryResultItem> + 'a>>,
@@ -78,16 +88,123 @@ fn classify_semver_version_change(
     }
 }
 
+/// How the lints that aren't [`LintLevel::Deny`] are run and reported.
+#[derive(Debug, Clone, Default)]
+pub(super) struct LintOptions {
+    /// Whether to run the [`LintLevel::Info`] lints.
+    pub(super) include_info: bool,
+    /// Whether violations of any lint fail the check, not just [`LintLevel::Deny`] ones.
+    pub(super) pedantic: bool,
+    /// Ids of [`LintLevel::Deny`] lints to run as [`LintLevel::Warn`] ones instead.
+    pub(super) warnings_only: BTreeSet<String>,
+    /// Lint levels replacing the default ones, by lint id.
+    pub(super) lint_levels: BTreeMap<String, LintLevel>,
+    /// Documentation links found broken by comparing the rustdoc files,
+    /// reported as violations of a [`LintLevel::Warn`] lint.
+    pub(super) broken_doc_links: Vec<BrokenDocLink>,
+}
+
+/// The importable paths of the items marked with [`SEMVER_EXEMPT_MARKER`],
+/// in either the baseline or the current version.
+fn exempt_paths(adapter: &VersionedRustdocAdapter) -> anyhow::Result<BTreeSet<Vec<String>>> {
+    let arguments: BTreeMap<String, TransparentValue> = [(
+        "marker".to_string(),
+        FieldValue::from(SEMVER_EXEMPT_MARKER).into(),
+    )]
+    .into();
+    let mut paths = BTreeSet::new();
+    for version in ["baseline", "current"] {
+        for item_type in ["ImplOwner", "Function", "Trait"] {
+            let query = format!(
+                r#"{{
+                    CrateDiff {{
+                        {version} {{
+                            item {{
+                                ... on {item_type} {{
+                                    docs @filter(op: "has_substring", value: ["$marker"])
+
+                                    importable_path {{
+                                        path @output
+                                    }}
+                                }}
+                            }}
+                        }}
+                    }}
+                }}"#
+            );
+            let results = adapter
+                .run_query(&query, arguments.clone())
+                .context("failed to find semver-exempt items")
+                .map_err(CheckError::Query)?;
+            for result in results {
+                if let Some(path) = result_path(&result) {
+                    paths.insert(path);
+                }
+            }
+        }
+    }
+    Ok(paths)
+}
+
+/// The importable path output by a query, which every lint is expected to output.
+fn result_path(result: &QueryResultItem) -> Option<Vec<String>> {
+    match result.get("path")? {
+        FieldValue::List(segments) => segments
+            .iter()
+            .map(|segment| segment.as_str().map(str::to_string))
+            .collect(),
+        _ => None,
+    }
+}
+
+/// The lint reporting [`BrokenDocLink`]s, found with [`crate::RustdocDiff`].
+///
+/// Links are compared in the rustdoc JSON directly rather than through a query,
+/// so this lint has no query text and isn't one of [`SemverQuery::all_queries()`].
+fn doc_link_broken_query() -> SemverQuery {
+    SemverQuery {
+        id: "doc_link_broken".to_string(),
+        human_readable_name: "documentation link no longer resolves".to_string(),
+        description:
+            "A documentation link no longer resolves, while the item containing it remains."
+                .to_string(),
+        // Broken links don't break downstream code, so this lint is never more than a warning.
+        required_update: RequiredSemverUpdate::Minor,
+        group: LintGroup::Other,
+        lint_level: LintLevel::Warn,
+        reference: None,
+        reference_link: None,
+        query: String::new(),
+        arguments: BTreeMap::new(),
+        error_message: "A documentation link pointed to an item that can no longer be found \
+            under that name, so the link is now broken in the rendered documentation."
+            .to_string(),
+        suggested_fix: Some(
+            "Update the link to the item's new name or path, or remove it.".to_string(),
+        ),
+        per_result_error_template: None,
+    }
+}
+
 pub(super) fn run_check_release(
     config: &mut GlobalConfig,
     crate_name: &str,
     current_crate: VersionedCrate,
     baseline_crate: VersionedCrate,
     release_type: Option<ReleaseType>,
+    options: LintOptions,
+    reporter: &mut dyn Reporter,
 ) -> anyhow::Result<CrateReport> {
     let current_version = current_crate.crate_version();
     let baseline_version = baseline_crate.crate_version();
 
+    reporter.on_package_start(
+        crate_name,
+        current_version
+            .and_then(|version| semver::Version::parse(version).ok())
+            .as_ref(),
+    )?;
+
     let version_change = release_type
         .map(Into::into)
         .or_else(|| classify_semver_version_change(current_version, baseline_version))
@@ -110,16 +227,45 @@ pub(super) fn run_check_release(
         None => "",
     };
 
-    let queries = SemverQuery::all_queries();
+    let mut queries = SemverQuery::all_queries();
+    for query_id in &options.warnings_only {
+        let query = queries
+            .get_mut(query_id)
+            .with_context(|| format!("unknown lint `{query_id}`, see `--list` for all lints"))?;
+        if query.lint_level == LintLevel::Deny {
+            query.lint_level = LintLevel::Warn;
+        }
+    }
+    for (query_id, lint_level) in &options.lint_levels {
+        let query = queries
+            .get_mut(query_id)
+            .with_context(|| format!("unknown lint `{query_id}`, see `--list` for all lints"))?;
+        query.lint_level = *lint_level;
+    }
 
     let current = VersionedIndexedCrate::new(&current_crate);
     let previous = VersionedIndexedCrate::new(&baseline_crate);
     let adapter = VersionedRustdocAdapter::new(&current, Some(&previous))?;
+    let exempt_paths = exempt_paths(&adapter)?;
     let mut queries_with_errors: Vec<QueryWithResults> = vec![];
 
     let queries_to_run: Vec<_> = queries
         .iter()
-        .filter(|(_, query)| !version_change.supports_requirement(query.required_update))
+        .filter(|(id, query)| match query.lint_level {
+            LintLevel::Deny => !version_change.supports_requirement(query.required_update),
+            // Lints demoted to warnings are only reported when they would have failed the check.
+            LintLevel::Warn
+                if options.warnings_only.contains(*id)
+                    && !options.lint_levels.contains_key(*id) =>
+            {
+                !version_change.supports_requirement(query.required_update)
+            }
+            // Warnings never fail the check, so they are worth reporting
+            // no matter how the version changed.
+            LintLevel::Warn => true,
+            LintLevel::Info => options.include_info,
+            LintLevel::Allow => false,
+        })
         .collect();
     let skipped_queries = queries.len().saturating_sub(queries_to_run.len());
 
@@ -156,7 +302,7 @@ pub(super) fn run_check_release(
             .verbose(|config| {
                 if config.is_stderr_tty() {
                     colored!(
-                        config.stderr(),
+                        config.shell_stderr(),
                         "{}{}{:>12}{} [{:9}] {:^18} {}",
                         fg!(Some(Color::Cyan)),
                         bold!(true),
@@ -166,16 +312,23 @@ pub(super) fn run_check_release(
                         category,
                         query_id,
                     )?;
-                    config.stderr().flush()?;
+                    config.shell_stderr().flush()?;
                 }
                 Ok(())
             })
             .expect("print failed");
 
         let start_instant = std::time::Instant::now();
-        let mut results_iter = adapter
-            .run_query(&semver_query.query, semver_query.arguments.clone())?
-            .peekable();
+        let results_iter: Box<dyn Iterator<Item = QueryResultItem>> = Box::new(
+            adapter
+                .run_query(&semver_query.query, semver_query.arguments.clone())
+                .with_context(|| format!("failed to run lint `{}`", semver_query.id))
+                .map_err(CheckError::Query)?
+                .filter(|result| {
+                    !matches!(result_path(result), Some(path) if exempt_paths.contains(&path))
+                }),
+        );
+        let mut results_iter = results_iter.peekable();
         let peeked = results_iter.peek();
         let time_to_decide = start_instant.elapsed();
         total_duration += time_to_decide;
@@ -184,9 +337,9 @@ pub(super) fn run_check_release(
             config
                 .verbose(|config| {
                     if config.is_stderr_tty() {
-                        write!(config.stderr(), "\r")?;
+                        write!(config.shell_stderr(), "\r")?;
                     }
-                    colored_ln(config.stderr(), |w| {
+                    colored_ln(config.shell_stderr(), |w| {
                         colored!(
                             w,
                             "{}{}{:>12}{} [{:>8.3}s] {:^18} {}",
@@ -208,9 +361,9 @@ pub(super) fn run_check_release(
             config
                 .verbose(|config| {
                     if config.is_stderr_tty() {
-                        write!(config.stderr(), "\r")?;
+                        write!(config.shell_stderr(), "\r")?;
                     }
-                    colored_ln(config.stderr(), |w| {
+                    colored_ln(config.shell_stderr(), |w| {
                         colored!(
                             w,
                             "{}{}{:>12}{} [{:>8.3}s] {:^18} {}",
@@ -229,172 +382,160 @@ pub(super) fn run_check_release(
         }
     }
 
-    if !queries_with_errors.is_empty() {
+    let failed_queries = queries_with_errors
+        .iter()
+        .filter(|query_with_results| {
+            options.pedantic || queries[query_with_results.name].lint_level == LintLevel::Deny
+        })
+        .count();
+    let warned_queries = queries_with_errors
+        .iter()
+        .filter(|query_with_results| {
+            !options.pedantic && queries[query_with_results.name].lint_level == LintLevel::Warn
+        })
+        .count();
+    let noted_queries = queries_with_errors.len() - failed_queries - warned_queries;
+    let mut warned = String::new();
+    if warned_queries > 0 {
+        warned.push_str(&format!(", {warned_queries} warned"));
+    }
+    if noted_queries > 0 {
+        warned.push_str(&format!(", {noted_queries} noted"));
+    }
+    let passed_queries = queries_to_run.len() - queries_with_errors.len();
+    if failed_queries > 0 {
         config
             .shell_print(
                 "Completed",
                 format_args!(
-                    "[{:>8.3}s] {} checks; {} passed, {} failed, {} unnecessary",
+                    "[{:>8.3}s] {} checks; {} passed, {} failed{}, {} unnecessary",
                     total_duration.as_secs_f32(),
                     queries_to_run.len(),
-                    queries_to_run.len() - queries_with_errors.len(),
-                    queries_with_errors.len(),
+                    passed_queries,
+                    failed_queries,
+                    warned,
                     skipped_queries,
                 ),
                 Color::Red,
                 true,
             )
             .expect("print failed");
-
-        let mut required_versions = vec![];
-
-        for query_with_results in queries_with_errors {
-            let semver_query = &queries[query_with_results.name];
-            required_versions.push(semver_query.required_update);
-            colored_ln(config.stdout(), |w| {
-                colored!(
-                    w,
-                    "\n--- failure {}: {} ---\n",
-                    &semver_query.id,
-                    &semver_query.human_readable_name,
-                )
-            })
-            .expect("print failed");
-
-            if let Some(ref_link) = semver_query.reference_link.as_deref() {
-                colored_ln(config.stdout(), |w| {
-                    colored!(
-                        w,
-                        "{}Description:{}\n{}\n{:>12} {}\n{:>12} {}\n",
-                        bold!(true),
-                        reset!(),
-                        &semver_query.error_message,
-                        "ref:",
-                        ref_link,
-                        "impl:",
-                        format!(
-                            "https://github.com/obi1kenobi/cargo-semver-check/tree/v{}/src/lints/{}.ron",
-                            crate_version!(),
-                            semver_query.id,
-                        )
-                    )
-                })
-                .expect("print failed");
-            } else {
-                colored_ln(config.stdout(), |w| {
-                    colored!(
-                        w,
-                        "{}Description:{}\n{}\n{:>12} {}\n",
-                        bold!(true),
-                        reset!(),
-                        &semver_query.error_message,
-                        "impl:",
-                        format!(
-                            "https://github.com/obi1kenobi/cargo-semver-check/tree/v{}/src/lints/{}.ron",
-                            crate_version!(),
-                            semver_query.id,
-                        )
-                    )
-                })
-                .expect("print failed");
-            }
-
-            colored_ln(config.stdout(), |w| {
-                colored!(w, "{}Failed in:{}", bold!(true), reset!(),)
-            })
-            .expect("print failed");
-
-            let start_instant = std::time::Instant::now();
-            for semver_violation_result in query_with_results.results {
-                let pretty_result: BTreeMap<Arc<str>, TransparentValue> = semver_violation_result
-                    .into_iter()
-                    .map(|(k, v)| (k, v.into()))
-                    .collect();
-
-                if let Some(template) = semver_query.per_result_error_template.as_deref() {
-                    let message = config
-                        .handlebars()
-                        .render_template(template, &pretty_result)
-                        .context("Error instantiating semver query template.")
-                        .expect("could not materialize template");
-                    colored_ln(config.stdout(), |w| colored!(w, "  {}", message,))
-                        .expect("print failed");
-
-                    config
-                        .extra_verbose(|config| {
-                            colored_ln(config.stdout(), |w| {
-                                let serde_pretty = serde_json::to_string_pretty(&pretty_result)
-                                    .expect("serde failed");
-                                let indented_serde = serde_pretty
-                                    .split('\n')
-                                    .map(|line| format!("    {line}"))
-                                    .join("\n");
-                                colored!(w, "    lint rule output values:\n{}", indented_serde)
-                            })
-                            .map_err(|e| e.into())
-                        })
-                        .expect("print failed");
-                } else {
-                    colored_ln(config.stdout(), |w| {
-                        colored!(
-                            w,
-                            "{}\n",
-                            serde_json::to_string_pretty(&pretty_result).expect("serde failed"),
-                        )
-                    })
-                    .expect("print failed");
-                }
-            }
-            total_duration += start_instant.elapsed();
-        }
-
-        let required_bump = if required_versions.contains(&RequiredSemverUpdate::Major) {
-            RequiredSemverUpdate::Major
-        } else if required_versions.contains(&RequiredSemverUpdate::Minor) {
-            RequiredSemverUpdate::Minor
-        } else {
-            unreachable!("{:?}", required_versions)
-        };
-
-        config
-            .shell_print(
-                "Final",
-                format_args!(
-                    "[{:>8.3}s] semver requires new {} version: {} major and {} minor checks failed",
-                    total_duration.as_secs_f32(),
-                    required_bump.as_str(),
-                    required_versions.iter().filter(|x| *x == &RequiredSemverUpdate::Major).count(),
-                    required_versions.iter().filter(|x| *x == &RequiredSemverUpdate::Minor).count(),
-                ),
-                Color::Red,
-                true,
-            )
-            .expect("print failed");
-
-        Ok(CrateReport {
-            required_bump: Some(required_bump.into()),
-            detected_bump: version_change,
-        })
     } else {
         config
             .shell_print(
                 "Completed",
                 format_args!(
-                    "[{:>8.3}s] {} checks; {} passed, {} unnecessary",
+                    "[{:>8.3}s] {} checks; {} passed{}, {} unnecessary",
                     total_duration.as_secs_f32(),
                     queries_to_run.len(),
-                    queries_to_run.len(),
+                    passed_queries,
+                    warned,
                     skipped_queries,
                 ),
                 Color::Green,
                 true,
             )
             .expect("print failed");
-        Ok(CrateReport {
-            detected_bump: version_change,
-            required_bump: None,
-        })
     }
+
+    let mut required_versions = vec![];
+    let mut violations = vec![];
+
+    for query_with_results in queries_with_errors {
+        let semver_query = Arc::new(queries[query_with_results.name].clone());
+        if semver_query.lint_level == LintLevel::Deny {
+            required_versions.push(semver_query.required_update);
+        }
+
+        let start_instant = std::time::Instant::now();
+        for semver_violation_result in query_with_results.results {
+            let pretty_result: BTreeMap<Arc<str>, TransparentValue> = semver_violation_result
+                .into_iter()
+                .map(|(k, v)| (k, v.into()))
+                .collect();
+
+            let message = semver_query
+                .per_result_error_template
+                .as_deref()
+                .map(|template| {
+                    config
+                        .handlebars()
+                        .render_template(template, &pretty_result)
+                        .context("Error instantiating semver query template.")
+                        .expect("could not materialize template")
+                });
+
+            let violation = Violation::new(
+                crate_name.to_string(),
+                Arc::clone(&semver_query),
+                pretty_result,
+                message,
+                options.pedantic || semver_query.lint_level == LintLevel::Deny,
+            );
+            reporter.on_violation(&violation)?;
+            violations.push(violation);
+        }
+        total_duration += start_instant.elapsed();
+    }
+
+    if !options.broken_doc_links.is_empty() {
+        let semver_query = Arc::new(doc_link_broken_query());
+        for link in &options.broken_doc_links {
+            let values: BTreeMap<Arc<str>, TransparentValue> = [
+                (
+                    Arc::from("path"),
+                    TransparentValue::List(
+                        link.path()
+                            .split("::")
+                            .map(|segment| TransparentValue::String(segment.to_string()))
+                            .collect(),
+                    ),
+                ),
+                (
+                    Arc::from("link"),
+                    TransparentValue::String(link.link().to_string()),
+                ),
+                (
+                    Arc::from("target"),
+                    TransparentValue::String(link.target().to_string()),
+                ),
+            ]
+            .into();
+            let message = format!(
+                "documentation of {} links to {} as [{}], which no longer resolves",
+                link.path(),
+                link.target(),
+                link.link(),
+            );
+            let violation = Violation::new(
+                crate_name.to_string(),
+                Arc::clone(&semver_query),
+                values,
+                Some(message),
+                options.pedantic,
+            );
+            reporter.on_violation(&violation)?;
+            violations.push(violation);
+        }
+    }
+
+    let required_bump = if required_versions.contains(&RequiredSemverUpdate::Major) {
+        Some(RequiredSemverUpdate::Major)
+    } else if required_versions.contains(&RequiredSemverUpdate::Minor) {
+        Some(RequiredSemverUpdate::Minor)
+    } else {
+        None
+    };
+
+    let report = CrateReport {
+        required_bump: required_bump.map(Into::into),
+        detected_bump: version_change,
+        violations,
+        duration: total_duration,
+        pedantic: options.pedantic,
+    };
+    reporter.on_package_complete(crate_name, &report)?;
+    Ok(report)
 }
 
 #[cfg(test)]
@@ -1,16 +1,68 @@
-use termcolor::{ColorChoice, StandardStream};
+use termcolor::{BufferWriter, ColorChoice, ColorSpec, StandardStream, WriteColor};
 
 use crate::templating::make_handlebars_registry;
 
 #[allow(dead_code)]
 pub struct GlobalConfig {
     level: Option<log::Level>,
+    /// Whether to capture the stderr of cargo subprocesses instead of forwarding it.
+    /// If `None`, it's captured unless running in verbose mode.
+    intercept_cargo_stderr: Option<bool>,
     is_stderr_tty: bool,
+    stdout_color: ColorChoice,
+    stderr_color: ColorChoice,
     stdout: StandardStream,
-    stderr: StandardStream,
+    stderr: Stderr,
     handlebars: handlebars::Handlebars<'static>,
 }
 
+/// Where a [`GlobalConfig`] writes its stderr output.
+pub(crate) enum Stderr {
+    Stream(StandardStream),
+    /// Output held back until [`GlobalConfig::print_buffered()`] is called.
+    /// The stream is only written to directly through [`GlobalConfig::stderr()`].
+    Buffer(StandardStream, termcolor::Buffer),
+}
+
+impl std::io::Write for Stderr {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Stream(stream) => stream.write(buf),
+            Self::Buffer(_, buffer) => buffer.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Stream(stream) => stream.flush(),
+            Self::Buffer(_, buffer) => buffer.flush(),
+        }
+    }
+}
+
+impl WriteColor for Stderr {
+    fn supports_color(&self) -> bool {
+        match self {
+            Self::Stream(stream) => stream.supports_color(),
+            Self::Buffer(_, buffer) => buffer.supports_color(),
+        }
+    }
+
+    fn set_color(&mut self, spec: &ColorSpec) -> std::io::Result<()> {
+        match self {
+            Self::Stream(stream) => stream.set_color(spec),
+            Self::Buffer(_, buffer) => buffer.set_color(spec),
+        }
+    }
+
+    fn reset(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Stream(stream) => stream.reset(),
+            Self::Buffer(_, buffer) => buffer.reset(),
+        }
+    }
+}
+
 impl Default for GlobalConfig {
     fn default() -> Self {
         Self::new()
@@ -30,27 +82,66 @@ impl GlobalConfig {
             Ok(_) | Err(..) => None,
         };
 
+        let stdout_color = color_choice.unwrap_or({
+            if is_stdout_tty {
+                ColorChoice::Auto
+            } else {
+                ColorChoice::Never
+            }
+        });
+        let stderr_color = color_choice.unwrap_or({
+            if is_stderr_tty {
+                ColorChoice::Auto
+            } else {
+                ColorChoice::Never
+            }
+        });
+
         Self {
             level: None,
+            intercept_cargo_stderr: None,
             is_stderr_tty,
-            stdout: StandardStream::stdout(color_choice.unwrap_or({
-                if is_stdout_tty {
-                    ColorChoice::Auto
-                } else {
-                    ColorChoice::Never
-                }
-            })),
-            stderr: StandardStream::stderr(color_choice.unwrap_or({
-                if is_stderr_tty {
-                    ColorChoice::Auto
-                } else {
-                    ColorChoice::Never
-                }
-            })),
+            stdout_color,
+            stderr_color,
+            stdout: StandardStream::stdout(stdout_color),
+            stderr: Stderr::Stream(StandardStream::stderr(stderr_color)),
             handlebars: make_handlebars_registry(),
         }
     }
 
+    /// A config with the same settings, whose stderr output is held back until
+    /// [`GlobalConfig::print_buffered()`] is called. This keeps the output of work
+    /// done in parallel, e.g. for several crates, from being interleaved.
+    pub(crate) fn buffered(&self) -> Self {
+        Self {
+            level: self.level,
+            intercept_cargo_stderr: self.intercept_cargo_stderr,
+            is_stderr_tty: self.is_stderr_tty,
+            stdout_color: self.stdout_color,
+            stderr_color: self.stderr_color,
+            stdout: StandardStream::stdout(self.stdout_color),
+            stderr: Stderr::Buffer(
+                StandardStream::stderr(self.stderr_color),
+                BufferWriter::stderr(self.stderr_color).buffer(),
+            ),
+            handlebars: make_handlebars_registry(),
+        }
+    }
+
+    /// Whether stderr output is held back, see [`GlobalConfig::buffered()`].
+    pub(crate) fn is_stderr_buffered(&self) -> bool {
+        matches!(self.stderr, Stderr::Buffer(..))
+    }
+
+    /// Print the stderr output held back by a [`GlobalConfig::buffered()`] config.
+    pub(crate) fn print_buffered(&mut self) -> anyhow::Result<()> {
+        if let Stderr::Buffer(_, buffer) = &mut self.stderr {
+            BufferWriter::stderr(self.stderr_color).print(buffer)?;
+            buffer.clear();
+        }
+        Ok(())
+    }
+
     pub fn handlebars(&self) -> &handlebars::Handlebars<'static> {
         &self.handlebars
     }
@@ -60,6 +151,22 @@ impl GlobalConfig {
         self
     }
 
+    /// Capture the stderr output of cargo's own builds instead of forwarding it,
+    /// so it doesn't get mixed with our output. It's still shown if the build fails.
+    ///
+    /// By default, cargo's stderr is only forwarded in verbose mode.
+    /// Checks set this through
+    /// [`Check::with_cargo_stderr_intercepted()`](crate::Check::with_cargo_stderr_intercepted).
+    pub fn intercept_cargo_stderr(mut self, yes: bool) -> Self {
+        self.intercept_cargo_stderr = Some(yes);
+        self
+    }
+
+    pub fn is_cargo_stderr_intercepted(&self) -> bool {
+        self.intercept_cargo_stderr
+            .unwrap_or_else(|| !self.is_verbose())
+    }
+
     pub fn is_verbose(&self) -> bool {
         log::Level::Debug <= self.level.unwrap_or(log::Level::Error)
     }
@@ -97,6 +204,14 @@ impl GlobalConfig {
     }
 
     pub fn stderr(&mut self) -> &mut StandardStream {
+        match &mut self.stderr {
+            Stderr::Stream(stream) | Stderr::Buffer(stream, _) => stream,
+        }
+    }
+
+    /// Where status messages and other stderr output of the check go:
+    /// the stderr stream, or the buffer of a [`GlobalConfig::buffered()`] config.
+    pub(crate) fn shell_stderr(&mut self) -> &mut Stderr {
         &mut self.stderr
     }
 
@@ -109,24 +224,23 @@ impl GlobalConfig {
         justified: bool,
     ) -> anyhow::Result<()> {
         use std::io::Write;
-        use termcolor::WriteColor;
 
-        self.stderr().set_color(
+        self.shell_stderr().set_color(
             termcolor::ColorSpec::new()
                 .set_fg(Some(color))
                 .set_bold(true),
         )?;
         if justified {
-            write!(self.stderr(), "{status:>12}")?;
+            write!(self.shell_stderr(), "{status:>12}")?;
         } else {
-            write!(self.stderr(), "{status}")?;
-            self.stderr()
+            write!(self.shell_stderr(), "{status}")?;
+            self.shell_stderr()
                 .set_color(termcolor::ColorSpec::new().set_bold(true))?;
-            write!(self.stderr(), ":")?;
+            write!(self.shell_stderr(), ":")?;
         }
-        self.stderr().reset()?;
+        self.shell_stderr().reset()?;
 
-        writeln!(self.stderr(), " {message}")?;
+        writeln!(self.shell_stderr(), " {message}")?;
 
         Ok(())
     }
@@ -148,3 +262,32 @@ impl GlobalConfig {
         self.shell_print("warning", message, termcolor::Color::Yellow, false)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{GlobalConfig, Stderr};
+
+    #[test]
+    fn buffered_config_keeps_settings_and_holds_back_stderr() {
+        let config = GlobalConfig::new()
+            .set_level(Some(log::Level::Debug))
+            .intercept_cargo_stderr(true);
+        let mut buffered = config.buffered();
+        assert!(buffered.is_verbose());
+        assert!(buffered.is_cargo_stderr_intercepted());
+        assert!(buffered.is_stderr_buffered());
+        assert!(!config.is_stderr_buffered());
+
+        buffered.shell_status("Testing", "buffered output").unwrap();
+        let Stderr::Buffer(_, buffer) = &buffered.stderr else {
+            panic!("stderr isn't buffered");
+        };
+        assert!(String::from_utf8_lossy(buffer.as_slice()).contains("buffered output"));
+
+        buffered.print_buffered().unwrap();
+        let Stderr::Buffer(_, buffer) = &buffered.stderr else {
+            panic!("stderr isn't buffered");
+        };
+        assert!(buffer.is_empty());
+    }
+}
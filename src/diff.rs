@@ -0,0 +1,367 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use anyhow::Context;
+use itertools::Itertools;
+
+/// The items of a rustdoc JSON file, keyed by their path within the crate.
+///
+/// Only items listed in the rustdoc's `paths` table as belonging to the crate itself
+/// are included: modules, types, traits, functions and the like,
+/// but not e.g. methods or fields, which have no path of their own.
+#[derive(Debug, Clone)]
+pub struct RustdocCrate {
+    items: BTreeMap<String, (String, serde_json::Value)>,
+    /// The intra-doc links of each item, from link text to the path of the linked item.
+    /// Links to items without a path of their own, such as methods, are not included.
+    links: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl RustdocCrate {
+    /// Load the rustdoc JSON file at the given path.
+    pub fn from_path(rustdoc_path: &Path) -> anyhow::Result<Self> {
+        let file = std::fs::File::open(rustdoc_path)
+            .with_context(|| format!("failed to open rustdoc JSON {}", rustdoc_path.display()))?;
+        let rustdoc: serde_json::Value = serde_json::from_reader(std::io::BufReader::new(file))
+            .with_context(|| format!("failed to parse rustdoc JSON {}", rustdoc_path.display()))?;
+        Self::from_json(rustdoc)
+            .with_context(|| format!("unexpected rustdoc JSON in {}", rustdoc_path.display()))
+    }
+
+    /// Build from already-parsed rustdoc JSON.
+    pub fn from_json(mut rustdoc: serde_json::Value) -> anyhow::Result<Self> {
+        let root_crate_id = rustdoc["index"][rustdoc["root"].as_str().unwrap_or_default()]
+            ["crate_id"]
+            .as_u64()
+            .context("missing root item")?;
+        let index = match rustdoc["index"].take() {
+            serde_json::Value::Object(index) => index,
+            _ => anyhow::bail!("missing item index"),
+        };
+        let paths = rustdoc["paths"].as_object().context("missing path table")?;
+
+        let join_path = |summary: &serde_json::Value| {
+            summary["path"].as_array().map(|path| {
+                path.iter()
+                    .filter_map(|segment| segment.as_str())
+                    .join("::")
+            })
+        };
+
+        let id_paths: HashMap<&str, String> = paths
+            .iter()
+            .filter_map(|(id, summary)| Some((id.as_str(), join_path(summary)?)))
+            .collect();
+
+        let mut items = BTreeMap::new();
+        let mut links = BTreeMap::new();
+        for (id, summary) in paths {
+            if summary["crate_id"].as_u64() != Some(root_crate_id) {
+                continue;
+            }
+            let Some(mut item) = index.get(id).cloned() else {
+                continue;
+            };
+            let path = join_path(summary).context("missing item path")?;
+            let kind = summary["kind"].as_str().unwrap_or_default().to_string();
+
+            let item_links: BTreeMap<String, String> = item["links"]
+                .as_object()
+                .into_iter()
+                .flatten()
+                .filter_map(|(text, target)| {
+                    let target = join_path(paths.get(target.as_str()?)?)?;
+                    Some((text.clone(), target))
+                })
+                .collect();
+            if !item_links.is_empty() {
+                links.insert(path.clone(), item_links);
+            }
+
+            strip_build_specific(&mut item);
+            normalize_ids(&mut item, &index, &id_paths, 0);
+            items.insert(path, (kind, item));
+        }
+
+        Ok(Self { items, links })
+    }
+}
+
+/// How deep to inline referenced items that have no path, in case they refer to each other.
+const MAX_INLINE_DEPTH: usize = 8;
+
+/// Ids and spans differ between otherwise-identical builds.
+fn strip_build_specific(item: &mut serde_json::Value) {
+    if let Some(item) = item.as_object_mut() {
+        item.remove("id");
+        item.remove("span");
+    }
+}
+
+/// Replace the ids an item refers to, e.g. the items of a module or the impls of a type,
+/// with something that stays the same between builds: the path of the referenced item
+/// if it has one, or else the referenced item itself, normalized the same way.
+fn normalize_ids(
+    value: &mut serde_json::Value,
+    index: &serde_json::Map<String, serde_json::Value>,
+    id_paths: &HashMap<&str, String>,
+    depth: usize,
+) {
+    let replacement = match value {
+        serde_json::Value::String(id) => {
+            if let Some(path) = id_paths.get(id.as_str()) {
+                Some(serde_json::Value::String(path.clone()))
+            } else if let Some(item) = index.get(id.as_str()).filter(|_| depth < MAX_INLINE_DEPTH) {
+                let mut item = item.clone();
+                strip_build_specific(&mut item);
+                normalize_ids(&mut item, index, id_paths, depth + 1);
+                Some(item)
+            } else {
+                None
+            }
+        }
+        serde_json::Value::Array(values) => {
+            for value in values {
+                normalize_ids(value, index, id_paths, depth);
+            }
+            None
+        }
+        serde_json::Value::Object(fields) => {
+            for (key, value) in fields {
+                // Docs are free text, and link texts are keys, so neither can hold an id.
+                if key != "docs" {
+                    normalize_ids(value, index, id_paths, depth);
+                }
+            }
+            None
+        }
+        _ => None,
+    };
+    if let Some(replacement) = replacement {
+        *value = replacement;
+    }
+}
+
+/// An item found in only one of the compared rustdoc files, or changed between them.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DiffItem {
+    path: String,
+    kind: String,
+}
+
+impl DiffItem {
+    /// Path of the item within its crate, e.g. `my_crate::my_mod::MyStruct`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Kind of the item as named by rustdoc, e.g. `struct` or `function`.
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+}
+
+/// A documentation link that worked in the baseline, but no longer resolves
+/// in the current version while the linking item and its link remain.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct BrokenDocLink {
+    path: String,
+    link: String,
+    target: String,
+}
+
+impl BrokenDocLink {
+    /// Path of the item whose documentation contains the link.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The link as written in the documentation, e.g. `` `MyType` ``.
+    pub fn link(&self) -> &str {
+        &self.link
+    }
+
+    /// Path of the item the link pointed to in the baseline.
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+}
+
+/// The raw structural difference between two rustdoc JSON files.
+///
+/// Unlike the semver lints, this doesn't judge whether a difference matters:
+/// it's meant as a starting point for custom analysis.
+/// An item counts as changed if its rustdoc JSON differs in anything but its id
+/// and span. Items it refers to, such as the items of a module or the impls of a type,
+/// are compared by path, or by their own contents if they have no path,
+/// since their ids differ between builds too.
+#[derive(Debug, Clone, Default)]
+pub struct RustdocDiff {
+    added: Vec<DiffItem>,
+    removed: Vec<DiffItem>,
+    changed: Vec<DiffItem>,
+    broken_doc_links: Vec<BrokenDocLink>,
+}
+
+impl RustdocDiff {
+    /// Compare the items of the baseline and current rustdoc.
+    pub fn compute(baseline: &RustdocCrate, current: &RustdocCrate) -> Self {
+        let mut diff = Self::default();
+        for (path, (kind, item)) in &baseline.items {
+            let diff_item = DiffItem {
+                path: path.clone(),
+                kind: kind.clone(),
+            };
+            match current.items.get(path) {
+                None => diff.removed.push(diff_item),
+                Some((current_kind, current_item)) => {
+                    if current_kind != kind || current_item != item {
+                        diff.changed.push(diff_item);
+                    }
+                }
+            }
+        }
+        for (path, (kind, _)) in &current.items {
+            if !baseline.items.contains_key(path) {
+                diff.added.push(DiffItem {
+                    path: path.clone(),
+                    kind: kind.clone(),
+                });
+            }
+        }
+        for (path, links) in &baseline.links {
+            let Some((_, current_item)) = current.items.get(path) else {
+                continue;
+            };
+            let docs = current_item["docs"].as_str().unwrap_or_default();
+            for (link, target) in links {
+                // Rustdoc only records the links it could resolve, so a link that is
+                // missing while its text is still in the docs no longer resolves.
+                // Links to items that moved, or were re-exported elsewhere, still do.
+                if current_item["links"].get(link.as_str()).is_none()
+                    && docs.contains(link.as_str())
+                {
+                    diff.broken_doc_links.push(BrokenDocLink {
+                        path: path.clone(),
+                        link: link.clone(),
+                        target: target.clone(),
+                    });
+                }
+            }
+        }
+        diff
+    }
+
+    /// Items only present in the current rustdoc, sorted by path.
+    pub fn added_items(&self) -> &[DiffItem] {
+        &self.added
+    }
+
+    /// Items only present in the baseline rustdoc, sorted by path.
+    pub fn removed_items(&self) -> &[DiffItem] {
+        &self.removed
+    }
+
+    /// Items present in both rustdoc files but different between them, sorted by path.
+    /// The kind is the one in the baseline rustdoc.
+    pub fn changed_items(&self) -> &[DiffItem] {
+        &self.changed
+    }
+
+    /// Documentation links of items present in both rustdoc files that resolved
+    /// in the baseline but no longer do, e.g. because their target was removed,
+    /// sorted by the path of the linking item.
+    ///
+    /// Broken links don't break compilation, so they aren't counted as a difference.
+    pub fn broken_doc_links(&self) -> &[BrokenDocLink] {
+        &self.broken_doc_links
+    }
+
+    /// Whether the two rustdoc files have the same items.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load(crate_pair: &str, crate_version: &str) -> RustdocCrate {
+        let path = format!("./localdata/test_data/{crate_pair}/{crate_version}/rustdoc.json");
+        RustdocCrate::from_path(Path::new(&path)).expect("failed to load rustdoc")
+    }
+
+    fn paths(items: &[DiffItem]) -> Vec<&str> {
+        items.iter().map(DiffItem::path).collect()
+    }
+
+    #[test]
+    fn diff_trait_missing() {
+        let baseline = load("trait_missing", "old");
+        let current = load("trait_missing", "new");
+        let diff = RustdocDiff::compute(&baseline, &current);
+
+        assert_eq!(
+            paths(diff.added_items()),
+            [
+                "trait_missing::AddedPrivateTrait",
+                "trait_missing::AddedPubTrait"
+            ]
+        );
+        assert_eq!(
+            paths(diff.removed_items()),
+            [
+                "trait_missing::PrivateTrait",
+                "trait_missing::RemovedTrait",
+                "trait_missing::RemovedUnsafeTrait",
+                "trait_missing::my_pub_mod",
+                "trait_missing::my_pub_mod::PubUseRemovedTrait",
+            ]
+        );
+        assert!(diff.changed_items().iter().any(|item| item.path()
+            == "trait_missing::TraitBecomesUnsafe"
+            && item.kind() == "trait"));
+    }
+
+    #[test]
+    fn diff_broken_doc_links() {
+        let baseline = load("doc_link_broken", "old");
+        let current = load("doc_link_broken", "new");
+        let diff = RustdocDiff::compute(&baseline, &current);
+
+        let broken = diff
+            .broken_doc_links()
+            .iter()
+            .map(|link| (link.path(), link.link(), link.target()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            broken,
+            [(
+                "doc_link_broken::Documented",
+                "`Builder`",
+                "doc_link_broken::Builder"
+            )]
+        );
+
+        let diff = RustdocDiff::compute(&baseline, &baseline);
+        assert!(diff.broken_doc_links().is_empty());
+    }
+
+    #[test]
+    fn diff_ignores_shifted_ids() {
+        // Removing items renumbers the ids of `Kept` and of its impls,
+        // without changing anything about them.
+        let baseline = load("doc_link_broken", "old");
+        let current = load("doc_link_broken", "new");
+        let diff = RustdocDiff::compute(&baseline, &current);
+        assert!(!paths(diff.changed_items()).contains(&"doc_link_broken::Kept"));
+    }
+
+    #[test]
+    fn diff_identical() {
+        let baseline = load("trait_missing", "old");
+        let diff = RustdocDiff::compute(&baseline, &baseline);
+        assert!(diff.is_empty());
+    }
+}
@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+/// The reason a semver check failed to run to completion.
+///
+/// This is distinct from a check that ran and found semver violations,
+/// which is reported through a successful [`Report`](crate::Report) instead.
+/// The underlying cause of each error is available through
+/// [`std::error::Error::source()`].
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+pub enum CheckError {
+    /// The current rustdoc couldn't be generated or loaded.
+    #[error("failed to generate the current rustdoc")]
+    Rustdoc(#[source] anyhow::Error),
+    /// The baseline couldn't be found or downloaded, or its rustdoc couldn't be
+    /// generated or loaded.
+    #[error("failed to get the baseline rustdoc")]
+    Baseline(#[source] anyhow::Error),
+    /// A lint query failed to execute.
+    #[error("failed to run a lint query")]
+    Query(#[source] anyhow::Error),
+    /// The check didn't complete within the time limit.
+    #[error("semver check did not complete within {}s", .timeout.as_secs_f32())]
+    Timeout {
+        timeout: Duration,
+        #[source]
+        source: anyhow::Error,
+    },
+    /// Any other failure, e.g. due to an invalid configuration or an I/O error.
+    #[error(transparent)]
+    Other(anyhow::Error),
+}
+
+impl From<anyhow::Error> for CheckError {
+    /// Recover a [`CheckError`] that was raised deeper in the check and then passed
+    /// around as an [`anyhow::Error`], or classify the error as [`CheckError::Other`].
+    fn from(err: anyhow::Error) -> Self {
+        err.downcast().unwrap_or_else(CheckError::Other)
+    }
+}
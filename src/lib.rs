@@ -2,8 +2,11 @@
 
 mod check_release;
 mod config;
+mod diff;
+mod error;
 mod manifest;
 mod query;
+mod reporter;
 mod rustdoc_cmd;
 mod rustdoc_gen;
 mod templating;
@@ -13,17 +16,26 @@ use anyhow::Context;
 use cargo_metadata::PackageId;
 use clap::ValueEnum;
 use directories::ProjectDirs;
+use itertools::Itertools;
+use rayon::prelude::*;
 
-use check_release::run_check_release;
+use check_release::{run_check_release, LintOptions};
+use reporter::BufferedReporter;
 use trustfall_rustdoc::{load_rustdoc, VersionedCrate};
 
-use rustdoc_cmd::RustdocCommand;
+use rustdoc_cmd::{DockerBuild, RustdocCommand};
 use semver::Version;
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 pub use config::GlobalConfig;
-pub use query::{ActualSemverUpdate, RequiredSemverUpdate, SemverQuery};
+pub use diff::{BrokenDocLink, DiffItem, RustdocCrate, RustdocDiff};
+pub use error::CheckError;
+pub use query::{ActualSemverUpdate, LintGroup, LintLevel, RequiredSemverUpdate, SemverQuery};
+pub use reporter::{
+    JsonReporter, JunitReporter, Reporter, SarifReporter, TerminalReporter, Violation,
+};
 
 /// Test a release for semver violations.
 #[non_exhaustive]
@@ -34,7 +46,41 @@ pub struct Check {
     current: Rustdoc,
     baseline: Rustdoc,
     log_level: Option<log::Level>,
+    /// Whether to capture the stderr of `cargo`, if not decided by the log level.
+    cargo_stderr_intercepted: Option<bool>,
     release_type: Option<ReleaseType>,
+    features_override: Option<FeatureOverride>,
+    /// Opaque key-value pairs recorded in the report, e.g. for audit purposes.
+    metadata: BTreeMap<String, String>,
+
+    /// Environment variables set on the spawned `cargo` processes.
+    environment: HashMap<String, String>,
+
+    /// Whether to pass `-Z build-std` when generating the current rustdoc.
+    current_build_std: bool,
+    /// Whether to pass `-Z build-std` when generating the baseline rustdoc.
+    baseline_build_std: bool,
+    /// Docker image to generate the baseline rustdoc in, if not on the host.
+    baseline_docker: Option<DockerBuild>,
+    /// Whether to reuse cached rustdoc of registry crates.
+    use_cache: bool,
+    /// Lint levels replacing the default ones, by lint id.
+    lint_levels: BTreeMap<String, LintLevel>,
+    /// Custom target spec JSON file to generate both rustdoc files for.
+    target_spec_json: Option<PathBuf>,
+    /// Local crates replacing crates.io dependencies of the same name in both builds.
+    dependency_overrides: Vec<(String, PathBuf)>,
+
+    /// Whether to run the informational lints that report documentation changes.
+    compare_docs: bool,
+    /// Whether to warn about documentation links broken by removed items.
+    check_doc_links: bool,
+    /// Whether violations of `warn` and `info` lints also fail the check.
+    pedantic: bool,
+    /// Ids of lints whose violations are reported as warnings instead of failing the check.
+    warnings_only: BTreeSet<String>,
+    /// Whether to skip packages whose version suggests they were never published.
+    skip_unpublished: bool,
 }
 
 /// The kind of release we're making.
@@ -49,6 +95,64 @@ pub enum ReleaseType {
     Patch,
 }
 
+/// Which crate features to enable when generating the current and baseline rustdoc.
+///
+/// By default, all features are enabled on both sides. Overriding that is useful when
+/// the two versions need different features to expose equivalent APIs,
+/// e.g. when an optional feature becomes enabled by default in the current version.
+#[non_exhaustive]
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct FeatureOverride {
+    /// Features to enable in the current version.
+    pub current_features: Vec<String>,
+    /// Features to enable in the baseline version.
+    pub baseline_features: Vec<String>,
+    /// If `true`, the default features of the current version are not enabled.
+    pub current_no_default: bool,
+    /// If `true`, the default features of the baseline version are not enabled.
+    pub baseline_no_default: bool,
+}
+
+impl FeatureOverride {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_current_features(&mut self, features: Vec<String>) -> &mut Self {
+        self.current_features = features;
+        self
+    }
+
+    pub fn with_baseline_features(&mut self, features: Vec<String>) -> &mut Self {
+        self.baseline_features = features;
+        self
+    }
+
+    pub fn with_current_no_default(&mut self, no_default: bool) -> &mut Self {
+        self.current_no_default = no_default;
+        self
+    }
+
+    pub fn with_baseline_no_default(&mut self, no_default: bool) -> &mut Self {
+        self.baseline_no_default = no_default;
+        self
+    }
+
+    fn current(&self) -> rustdoc_gen::FeaturesToEnable<'_> {
+        rustdoc_gen::FeaturesToEnable::Selected {
+            features: &self.current_features,
+            default_features: !self.current_no_default,
+        }
+    }
+
+    fn baseline(&self) -> rustdoc_gen::FeaturesToEnable<'_> {
+        rustdoc_gen::FeaturesToEnable::Selected {
+            features: &self.baseline_features,
+            default_features: !self.baseline_no_default,
+        }
+    }
+}
+
 #[non_exhaustive]
 #[derive(Debug)]
 pub struct Rustdoc {
@@ -63,6 +167,17 @@ impl Rustdoc {
         }
     }
 
+    /// Download an existing rustdoc file, e.g. a build artifact published by CI.
+    ///
+    /// The file is downloaded with `curl`, which must be installed, so any URL
+    /// it supports works, including `https://` and `file://` URLs.
+    /// It's downloaded again on every check, since its content may change.
+    pub fn from_remote_url(url: impl Into<String>) -> Self {
+        Self {
+            source: RustdocSource::RemoteUrl(url.into()),
+        }
+    }
+
     /// Generate the rustdoc file from the project root directory,
     /// i.e. the directory containing the crate source.
     /// It can be a workspace or a single package.
@@ -83,6 +198,35 @@ impl Rustdoc {
         }
     }
 
+    /// Generate the rustdoc file from the project at a given commit of a GitHub repository,
+    /// without needing a local clone of it.
+    ///
+    /// The source archive of the commit is downloaded from the GitHub API with `curl`
+    /// and extracted with `tar`, which must both be installed. If the `GITHUB_TOKEN`
+    /// environment variable is set, it's used to authenticate with GitHub,
+    /// e.g. to access private repositories.
+    pub fn from_github(
+        owner: impl Into<String>,
+        repo: impl Into<String>,
+        sha: impl Into<String>,
+    ) -> Self {
+        Self {
+            source: RustdocSource::GitHub(owner.into(), repo.into(), sha.into()),
+        }
+    }
+
+    /// Generate the rustdoc file from the crate source in a `.zip` or `.tar.gz` archive,
+    /// e.g. a source release published for Windows users.
+    ///
+    /// The archive is extracted with `tar`, which must be installed. Reading `.zip` archives
+    /// needs bsdtar, which is the `tar` of Windows and macOS; elsewhere it's usually
+    /// packaged as `libarchive-tools`, and it must come first in `PATH` as `tar`.
+    pub fn from_archive(archive_path: impl Into<PathBuf>) -> Self {
+        Self {
+            source: RustdocSource::Archive(archive_path.into()),
+        }
+    }
+
     /// Generate the rustdoc file from the largest-numbered non-yanked non-prerelease version
     /// published to the cargo registry. If no such version, uses
     /// the largest-numbered version including yanked and prerelease versions.
@@ -98,6 +242,23 @@ impl Rustdoc {
             source: RustdocSource::VersionFromRegistry(Some(crate_version.into())),
         }
     }
+
+    /// Generate the rustdoc file from a specific crate version published
+    /// to the registry with the given git index URL, instead of crates.io.
+    ///
+    /// The crate is downloaded by cargo, so registry credentials
+    /// and `[source]` replacement configured for cargo apply.
+    pub fn from_registry_url(
+        registry_index_url: impl Into<String>,
+        crate_version: impl Into<String>,
+    ) -> Self {
+        Self {
+            source: RustdocSource::VersionFromRegistryUrl(
+                registry_index_url.into(),
+                crate_version.into(),
+            ),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -105,16 +266,24 @@ enum RustdocSource {
     /// Path to the Rustdoc json file.
     /// Use this option when you have already generated the rustdoc file.
     Rustdoc(PathBuf),
+    /// URL of the rustdoc json file to download.
+    RemoteUrl(String),
     /// Project root directory, i.e. the directory containing the crate source.
     /// It can be a workspace or a single package.
     Root(PathBuf),
     /// Project root directory and Git Revision.
     Revision(PathBuf, String),
+    /// Owner, name and commit of a GitHub repository containing the crate source.
+    GitHub(String, String, String),
+    /// Path to a `.zip` or `.tar.gz` archive containing the crate source.
+    Archive(PathBuf),
     /// Version from cargo registry to lookup. E.g. "1.0.0".
     /// If `None`, uses the largest-numbered non-yanked non-prerelease version
     /// published to the cargo registry. If no such version, uses
     /// the largest-numbered version including yanked and prerelease versions.
     VersionFromRegistry(Option<String>),
+    /// Git index URL of a registry other than crates.io, and the version to lookup in it.
+    VersionFromRegistryUrl(String, String),
 }
 
 /// Which packages to analyze.
@@ -142,6 +311,8 @@ impl Default for ScopeMode {
 pub struct PackageSelection {
     selection: ScopeSelection,
     excluded_packages: Vec<String>,
+    /// Manifest of the workspace or package to select packages from, if not the current one.
+    manifest_path: Option<PathBuf>,
 }
 
 impl PackageSelection {
@@ -149,6 +320,7 @@ impl PackageSelection {
         Self {
             selection,
             excluded_packages: vec![],
+            manifest_path: None,
         }
     }
 
@@ -156,6 +328,17 @@ impl PackageSelection {
         self.excluded_packages = packages;
         self
     }
+
+    /// Select packages from the workspace or package with the given `Cargo.toml`
+    /// (or the directory containing it), instead of from the current project root.
+    ///
+    /// This only affects which packages are checked, not where their current
+    /// rustdoc comes from. It also lets the packages be discovered when the current
+    /// rustdoc doesn't come from a project root, e.g. when it's from a git revision.
+    pub fn with_manifest_path(&mut self, path: PathBuf) -> &mut Self {
+        self.manifest_path = Some(path);
+        self
+    }
 }
 
 #[non_exhaustive]
@@ -169,6 +352,14 @@ pub enum ScopeSelection {
 }
 
 impl Scope {
+    /// The manifest to discover packages from, if set through [`PackageSelection`].
+    fn manifest_path(&self) -> Option<&Path> {
+        match &self.mode {
+            ScopeMode::DenyList(selection) => selection.manifest_path.as_deref(),
+            ScopeMode::AllowList(_) => None,
+        }
+    }
+
     fn selected_packages<'m>(
         &self,
         meta: &'m cargo_metadata::Metadata,
@@ -178,6 +369,7 @@ impl Scope {
             ScopeMode::DenyList(PackageSelection {
                 selection,
                 excluded_packages,
+                ..
             }) => {
                 let packages = match selection {
                     ScopeSelection::Workspace => workspace_members,
@@ -226,7 +418,23 @@ impl Check {
             current,
             baseline: Rustdoc::from_registry_latest_crate_version(),
             log_level: Default::default(),
+            cargo_stderr_intercepted: None,
             release_type: None,
+            features_override: None,
+            metadata: BTreeMap::new(),
+            environment: HashMap::new(),
+            current_build_std: false,
+            baseline_build_std: false,
+            baseline_docker: None,
+            use_cache: true,
+            lint_levels: BTreeMap::new(),
+            target_spec_json: None,
+            dependency_overrides: Vec::new(),
+            compare_docs: false,
+            check_doc_links: false,
+            pedantic: false,
+            warnings_only: BTreeSet::new(),
+            skip_unpublished: false,
         }
     }
 
@@ -245,16 +453,265 @@ impl Check {
         self
     }
 
+    /// Use the given version published to a registry other than crates.io as the baseline.
+    /// See [`Rustdoc::from_registry_url()`].
+    pub fn with_baseline_registry(&mut self, url: String, version: String) -> &mut Self {
+        self.baseline = Rustdoc::from_registry_url(url, version);
+        self
+    }
+
     pub fn with_log_level(&mut self, log_level: log::Level) -> &mut Self {
         self.log_level = Some(log_level);
         self
     }
 
+    /// Whether to capture the stderr output of the `cargo` processes that generate rustdoc,
+    /// instead of forwarding it. Captured output is still shown if `cargo` fails.
+    ///
+    /// By default, it's only forwarded when the log level is `Debug` or more verbose.
+    /// See [`GlobalConfig::intercept_cargo_stderr()`].
+    pub fn with_cargo_stderr_intercepted(&mut self, yes: bool) -> &mut Self {
+        self.cargo_stderr_intercepted = Some(yes);
+        self
+    }
+
     pub fn with_release_type(&mut self, release_type: ReleaseType) -> &mut Self {
         self.release_type = Some(release_type);
         self
     }
 
+    /// Assume the release must not contain any breaking or minor-level changes,
+    /// regardless of how its version number changed.
+    ///
+    /// This is the same as setting the release type to [`ReleaseType::Patch`], so it
+    /// replaces both the version change detected from the crate versions and any
+    /// release type set before. All [`LintLevel::Deny`] lints are run, and a violation
+    /// of any of them fails the check. Violations of [`LintLevel::Warn`] lints still
+    /// don't fail it, and [`LintLevel::Info`] lints only run if requested: combine this
+    /// with [`Check::with_pedantic()`] to fail on any violation.
+    pub fn with_assume_no_break(&mut self) -> &mut Self {
+        self.with_release_type(ReleaseType::Patch)
+    }
+
+    /// Enable the given features instead of all features
+    /// when generating the current and baseline rustdoc.
+    pub fn with_features_override(&mut self, features_override: FeatureOverride) -> &mut Self {
+        self.features_override = Some(features_override);
+        self
+    }
+
+    /// Enable the given features, plus the default ones, instead of all features
+    /// when generating both the current and baseline rustdoc.
+    ///
+    /// Default features stay disabled if [`Check::with_default_features()`]
+    /// turned them off.
+    ///
+    /// For different features on each side, use [`Check::with_features_override()`].
+    pub fn with_feature_flags(&mut self, features: Vec<String>) -> &mut Self {
+        let mut features_override = self.features_override.take().unwrap_or_default();
+        features_override
+            .with_current_features(features.clone())
+            .with_baseline_features(features);
+        self.with_features_override(features_override)
+    }
+
+    /// Whether to enable the default features when generating both the current
+    /// and baseline rustdoc, like cargo's `--no-default-features` when `false`.
+    ///
+    /// Disabling them also stops enabling all features: only the features selected
+    /// with [`Check::with_feature_flags()`] are enabled, if any,
+    /// which allows checking the crate's minimal feature set.
+    pub fn with_default_features(&mut self, enabled: bool) -> &mut Self {
+        if enabled && self.features_override.is_none() {
+            // All features are enabled, which includes the default ones.
+            return self;
+        }
+        let features_override = self.features_override.get_or_insert_with(Default::default);
+        features_override
+            .with_current_no_default(!enabled)
+            .with_baseline_no_default(!enabled);
+        self
+    }
+
+    /// Enable all features when generating the current and baseline rustdoc,
+    /// like cargo's `--all-features`. This is the default, so this method
+    /// only undoes a previous feature selection.
+    pub fn with_all_features(&mut self) -> &mut Self {
+        self.features_override = None;
+        self
+    }
+
+    /// Record a key-value pair in the report's metadata.
+    ///
+    /// The metadata is not interpreted by the check itself; it's carried through
+    /// to the report so that downstream tooling can tell how the check was performed.
+    pub fn with_metadata(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Set the given environment variables on the `cargo` processes spawned
+    /// to read manifests and generate rustdoc, e.g. `RUSTFLAGS` or `CARGO_TARGET_DIR`.
+    ///
+    /// The environment of the current process is left untouched.
+    /// A `RUSTDOCFLAGS` value is passed to rustdoc in addition to the flags
+    /// needed to generate rustdoc JSON. Cached baseline rustdoc is only reused
+    /// when it was generated with the same environment.
+    pub fn with_environment(&mut self, environment: HashMap<String, String>) -> &mut Self {
+        self.environment = environment;
+        self
+    }
+
+    /// Pass `-Z build-std` to `cargo` when generating the current rustdoc,
+    /// e.g. for `#![no_std]` crates targeting platforms without a prebuilt standard library.
+    ///
+    /// `build-std` needs an explicit target, which can be set through
+    /// `CARGO_BUILD_TARGET` with [`Check::with_environment()`].
+    pub fn with_current_build_std(&mut self) -> &mut Self {
+        self.current_build_std = true;
+        self
+    }
+
+    /// Generate the baseline rustdoc by running `cargo` in a container of the given
+    /// Docker image, for a baseline build that doesn't depend on the host's environment.
+    ///
+    /// The image must provide `cargo` with a toolchain that emits the same rustdoc JSON
+    /// format version as the host toolchain, which generates the current rustdoc.
+    /// The container's cargo home is kept in `cargo_home` on the host, so downloads
+    /// are reused between runs. If `None`, a directory in the target directory is used.
+    pub fn with_baseline_docker(
+        &mut self,
+        image: impl Into<String>,
+        cargo_home: Option<PathBuf>,
+    ) -> &mut Self {
+        self.baseline_docker = Some(DockerBuild {
+            image: image.into(),
+            cargo_home,
+        });
+        self
+    }
+
+    /// Generate both the current and baseline rustdoc for the custom target
+    /// described by the given target spec JSON file, by passing it as `--target` to `cargo`.
+    ///
+    /// Targets without a prebuilt standard library usually also need
+    /// [`Check::with_current_build_std()`] and [`Check::with_baseline_build_std()`].
+    pub fn with_target_spec_json(&mut self, target_spec: impl Into<PathBuf>) -> &mut Self {
+        self.target_spec_json = Some(target_spec.into());
+        self
+    }
+
+    /// Replace crates.io dependencies by name with the crates at the given local paths,
+    /// when generating both the current and baseline rustdoc.
+    ///
+    /// This is the same as adding a `[patch.crates-io]` entry for each override,
+    /// and helps determine whether a semver violation comes from a dependency.
+    /// Rustdoc generated with overrides is never cached.
+    pub fn with_dependency_overrides(&mut self, overrides: Vec<(String, PathBuf)>) -> &mut Self {
+        self.dependency_overrides = overrides;
+        self
+    }
+
+    /// Same as [`Check::with_current_build_std()`], but for the baseline rustdoc.
+    pub fn with_baseline_build_std(&mut self) -> &mut Self {
+        self.baseline_build_std = true;
+        self
+    }
+
+    /// Also report public items whose documentation changed, e.g. for changelog automation.
+    ///
+    /// Documentation changes are not semver violations. They are reported
+    /// with [`LintLevel::Info`], and never affect whether the check succeeds.
+    pub fn with_compare_docs(&mut self) -> &mut Self {
+        self.compare_docs = true;
+        self
+    }
+
+    /// Report documentation links, such as `` [`MyType`] ``, that no longer resolve,
+    /// in items that are still present along with the link.
+    ///
+    /// Broken links are reported as violations of the `doc_link_broken` lint
+    /// at [`LintLevel::Warn`]: they don't break downstream code, so they only fail
+    /// the check in [pedantic mode](Check::with_pedantic).
+    /// See [`RustdocDiff::broken_doc_links()`].
+    pub fn with_check_doc_links(&mut self) -> &mut Self {
+        self.check_doc_links = true;
+        self
+    }
+
+    /// Fail the check on any violation, including those of [`LintLevel::Warn`]
+    /// and [`LintLevel::Info`] lints that normally never affect the result.
+    ///
+    /// This doesn't enable any extra lints by itself: to also fail on
+    /// documentation changes, combine it with [`Check::with_compare_docs()`].
+    pub fn with_pedantic(&mut self) -> &mut Self {
+        self.pedantic = true;
+        self
+    }
+
+    /// Report violations of the lints with the given ids as warnings, which don't fail
+    /// the check, e.g. to phase in newly-added lints without breaking CI.
+    ///
+    /// This only affects lints that would otherwise fail the check, i.e. those with
+    /// [`LintLevel::Deny`]. The check returns an error if there's no lint with one
+    /// of the given ids. See also [`Check::with_pedantic()`], which takes precedence.
+    pub fn with_warnings_only(&mut self, lint_ids: Vec<String>) -> &mut Self {
+        self.warnings_only = lint_ids.into_iter().collect();
+        self
+    }
+
+    /// Regenerate the rustdoc of registry crates even if it was cached by a previous run.
+    ///
+    /// Rustdoc of crates downloaded from the registry is cached in
+    /// `target/semver-checks/cache/`, keyed by crate name, version and enabled features.
+    /// The freshly generated rustdoc replaces the cached copy.
+    /// Rustdoc of local crates is never cached, since their source may change at any time.
+    pub fn with_no_cache(&mut self) -> &mut Self {
+        self.use_cache = false;
+        self
+    }
+
+    /// Override the level of the lint with the given id, e.g. `function_missing`.
+    ///
+    /// [`LintLevel::Allow`] disables the lint, [`LintLevel::Warn`] reports its violations
+    /// without failing the check, and [`LintLevel::Deny`] fails the check on its violations
+    /// unless the version change already allows them. The check returns an error
+    /// if there's no lint with the given id.
+    pub fn with_lint_level(&mut self, id: impl Into<String>, level: LintLevel) -> &mut Self {
+        self.lint_levels.insert(id.into(), level);
+        self
+    }
+
+    /// Skip packages whose version suggests they have never been published,
+    /// instead of failing to find their baseline in the registry.
+    ///
+    /// A package is considered unpublished if its version is `0.0.0`, or if it's
+    /// a `dev` pre-release such as `0.1.0-dev`. This only applies to packages
+    /// checked from a project root, since other sources have no version to go by.
+    pub fn with_skip_unpublished(&mut self) -> &mut Self {
+        self.skip_unpublished = true;
+        self
+    }
+
+    /// The config to print messages and run `cargo` with.
+    fn global_config(&self) -> GlobalConfig {
+        let config = GlobalConfig::new().set_level(self.log_level);
+        match self.cargo_stderr_intercepted {
+            Some(yes) => config.intercept_cargo_stderr(yes),
+            None => config,
+        }
+    }
+
+    fn lint_options(&self) -> LintOptions {
+        LintOptions {
+            include_info: self.compare_docs,
+            pedantic: self.pedantic,
+            warnings_only: self.warnings_only.clone(),
+            lint_levels: self.lint_levels.clone(),
+            broken_doc_links: vec![],
+        }
+    }
+
     /// Some `RustdocSource`s don't contain a path to the project root,
     /// so they don't have a target directory. We try to deduce the target directory
     /// on a "best effort" basis -- when the source contains a target dir,
@@ -264,11 +721,15 @@ impl Check {
     /// are being cached in the target directory.
     fn get_target_dir(&self, source: &RustdocSource) -> anyhow::Result<PathBuf> {
         Ok(
-            if let Some(path) = get_target_dir_from_project_root(source)? {
+            if let Some(path) = get_target_dir_from_project_root(source, &self.environment)? {
                 path
-            } else if let Some(path) = get_target_dir_from_project_root(&self.current.source)? {
+            } else if let Some(path) =
+                get_target_dir_from_project_root(&self.current.source, &self.environment)?
+            {
                 path
-            } else if let Some(path) = get_target_dir_from_project_root(&self.baseline.source)? {
+            } else if let Some(path) =
+                get_target_dir_from_project_root(&self.baseline.source, &self.environment)?
+            {
                 path
             } else {
                 get_cache_dir()?
@@ -280,17 +741,24 @@ impl Check {
         &self,
         config: &mut GlobalConfig,
         source: &RustdocSource,
+        deadline: Option<Instant>,
     ) -> anyhow::Result<Box<dyn rustdoc_gen::RustdocGenerator>> {
         let target_dir = self.get_target_dir(source)?;
         Ok(match source {
             RustdocSource::Rustdoc(path) => {
                 Box::new(rustdoc_gen::RustdocFromFile::new(path.to_owned()))
             }
+            RustdocSource::RemoteUrl(url) => Box::new(rustdoc_gen::RustdocFromFile::download(
+                url,
+                &target_dir,
+                deadline,
+                config,
+            )?),
             RustdocSource::Root(root) => {
                 Box::new(rustdoc_gen::RustdocFromProjectRoot::new(root, &target_dir)?)
             }
             RustdocSource::Revision(root, rev) => {
-                let metadata = manifest_metadata_no_deps(root)?;
+                let metadata = manifest_metadata_no_deps(root, &self.environment)?;
                 let source = metadata.workspace_root.as_std_path();
                 Box::new(rustdoc_gen::RustdocFromGitRevision::with_rev(
                     source,
@@ -299,6 +767,26 @@ impl Check {
                     config,
                 )?)
             }
+            RustdocSource::GitHub(owner, repo, sha) => {
+                let target = target_dir.join(format!(
+                    "github-{}-{}-{}",
+                    util::slugify(owner),
+                    util::slugify(repo),
+                    util::slugify(sha)
+                ));
+                Box::new(rustdoc_gen::RustdocFromGitHub::download(
+                    owner, repo, sha, &target, deadline, config,
+                )?)
+            }
+            RustdocSource::Archive(archive) => {
+                let target = target_dir.join(format!(
+                    "archive-{}",
+                    util::slugify(&archive.to_string_lossy())
+                ));
+                Box::new(rustdoc_gen::RustdocFromArchive::extract(
+                    archive, &target, config,
+                )?)
+            }
             RustdocSource::VersionFromRegistry(version) => {
                 let mut registry = rustdoc_gen::RustdocFromRegistry::new(&target_dir, config)?;
                 if let Some(ver) = version {
@@ -307,17 +795,157 @@ impl Check {
                 }
                 Box::new(registry)
             }
+            RustdocSource::VersionFromRegistryUrl(url, version) => {
+                let mut registry =
+                    rustdoc_gen::RustdocFromRegistry::with_index_url(&target_dir, url, config)?;
+                registry.set_version(semver::Version::parse(version)?);
+                Box::new(registry)
+            }
         })
     }
 
-    pub fn check_release(&self) -> anyhow::Result<Report> {
-        let mut config = GlobalConfig::new().set_level(self.log_level);
+    pub fn check_release(&self) -> Result<Report, CheckError> {
+        let reporter = TerminalReporter::new(self.global_config());
+        self.check_release_with_reporter(reporter)
+    }
+
+    /// Same as [`Check::check_release()`], but semver violations and results
+    /// are passed to the given [`Reporter`] instead of being printed to the terminal.
+    ///
+    /// Progress messages, such as which crates are being parsed, are still printed.
+    pub fn check_release_with_reporter<R: Reporter>(
+        &self,
+        reporter: R,
+    ) -> Result<Report, CheckError> {
+        let report = self
+            .run_checks(reporter, false, None, self.use_cache)?
+            .expect("a report is always produced when unchanged crates aren't skipped");
+        Ok(report)
+    }
+
+    /// Same as [`Check::check_release()`], but crates whose API is unchanged between
+    /// the baseline and current rustdoc are skipped without running any lints.
+    ///
+    /// Returns `None` if all checked crates were unchanged. Otherwise, the report
+    /// only contains the crates that changed.
+    ///
+    /// The rustdoc of both versions still has to be generated in order to compare them.
+    /// The comparison ignores the crate version and source locations, but is otherwise
+    /// strict: e.g. an edited doc comment counts as a change.
+    pub fn try_check_release(&self) -> Result<Option<Report>, CheckError> {
+        let reporter = TerminalReporter::new(self.global_config());
+        Ok(self.run_checks(reporter, true, None, self.use_cache)?)
+    }
+
+    /// Same as [`Check::check_release()`], but returns an error if the check
+    /// doesn't complete within the given time limit.
+    ///
+    /// A `cargo doc` process that is still running when the limit is reached is killed.
+    /// The limit is also checked before each crate is processed, but other steps
+    /// like looking up the registry index are not interrupted.
+    pub fn check_release_with_timeout(&self, timeout: Duration) -> Result<Report, CheckError> {
+        let reporter = TerminalReporter::new(self.global_config());
+        self.check_release_with_reporter_and_timeout(reporter, timeout, self.use_cache)
+    }
+
+    /// Same as [`Check::check_release_with_ci_defaults()`] when running in a CI environment,
+    /// and exactly like [`Check::check_release()`] otherwise.
+    ///
+    /// A CI environment is detected through the `CI`, `GITHUB_ACTIONS` and `CIRCLECI`
+    /// environment variables.
+    pub fn check_release_in_ci(&self) -> Result<Report, CheckError> {
+        if !is_ci() {
+            return self.check_release();
+        }
+        self.check_release_with_ci_defaults()
+    }
+
+    /// Same as [`Check::check_release()`], but with defaults suited to CI:
+    /// - the rustdoc of registry crates is regenerated instead of being reused
+    ///   from the cache, as with [`Check::with_no_cache()`];
+    /// - the report is also written in JSON form, as returned by [`Report::to_json()`],
+    ///   to [`Check::ci_report_path()`], for later CI steps to consume;
+    /// - each lint that found violations is printed with its full explanation,
+    ///   since the log is often all a reader has to go on;
+    /// - the check fails if it doesn't complete within [`Check::CI_TIMEOUT`].
+    ///
+    /// Cargo's `--locked` and `--frozen` flags are not passed, since rustdoc is generated
+    /// through a placeholder project that has no lockfile, and the baseline usually
+    /// has to be downloaded.
+    pub fn check_release_with_ci_defaults(&self) -> Result<Report, CheckError> {
+        let reporter = TerminalReporter::new(self.global_config()).with_explanations(true);
+        let report =
+            self.check_release_with_reporter_and_timeout(reporter, Self::CI_TIMEOUT, false)?;
+
+        let path = self.ci_report_path()?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create {}", dir.display()))?;
+        }
+        let file = std::fs::File::create(&path)
+            .with_context(|| format!("failed to create {}", path.display()))?;
+        JsonReporter::new(std::io::BufWriter::new(file)).on_complete(&report)?;
+        Ok(report)
+    }
+
+    /// Time limit applied by [`Check::check_release_with_ci_defaults()`].
+    pub const CI_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+    /// Where [`Check::check_release_with_ci_defaults()`] writes the JSON report:
+    /// `semver-checks/report.json` in the target directory of the current crate.
+    pub fn ci_report_path(&self) -> anyhow::Result<PathBuf> {
+        Ok(self
+            .get_target_dir(&self.current.source)?
+            .join(util::SCOPE)
+            .join("report.json"))
+    }
+
+    fn check_release_with_reporter_and_timeout<R: Reporter>(
+        &self,
+        reporter: R,
+        timeout: Duration,
+        use_cache: bool,
+    ) -> Result<Report, CheckError> {
+        let deadline = Instant::now() + timeout;
+        let report = self
+            .run_checks(reporter, false, Some(deadline), use_cache)
+            .map_err(
+                |err| match Instant::now().checked_duration_since(deadline) {
+                    Some(_) => CheckError::Timeout {
+                        timeout,
+                        source: err,
+                    },
+                    None => err.into(),
+                },
+            )?
+            .expect("a report is always produced when unchanged crates aren't skipped");
+        Ok(report)
+    }
+
+    fn run_checks<R: Reporter>(
+        &self,
+        mut reporter: R,
+        skip_unchanged: bool,
+        deadline: Option<Instant>,
+        use_cache: bool,
+    ) -> anyhow::Result<Option<Report>> {
+        let mut config = self.global_config();
         let rustdoc_cmd = RustdocCommand::new()
             .deps(false)
-            .silence(!config.is_verbose());
+            .env(self.environment.clone())
+            .deadline(deadline)
+            .use_cache(use_cache)
+            .target_spec(self.target_spec_json.clone())
+            .patches(self.dependency_overrides.clone());
 
-        let current_loader = self.get_rustdoc_generator(&mut config, &self.current.source)?;
-        let baseline_loader = self.get_rustdoc_generator(&mut config, &self.baseline.source)?;
+        let current_loader = self
+            .get_rustdoc_generator(&mut config, &self.current.source, deadline)
+            .map_err(CheckError::Rustdoc)?;
+        let baseline_loader = self
+            .get_rustdoc_generator(&mut config, &self.baseline.source, deadline)
+            .map_err(CheckError::Baseline)?;
+
+        let mut unchanged_crates = 0;
 
         // Create a report for each crate.
         // We want to run all the checks, even if one returns `Err`.
@@ -326,12 +954,27 @@ impl Check {
             .source
         {
             RustdocSource::Rustdoc(_)
+            | RustdocSource::RemoteUrl(_)
             | RustdocSource::Revision(_, _)
-            | RustdocSource::VersionFromRegistry(_) => {
+            | RustdocSource::GitHub(_, _, _)
+            | RustdocSource::Archive(_)
+            | RustdocSource::VersionFromRegistry(_)
+            | RustdocSource::VersionFromRegistryUrl(_, _) => {
                 let names = match &self.scope.mode {
+                    ScopeMode::DenyList(PackageSelection {
+                        manifest_path: Some(manifest_path),
+                        ..
+                    }) => {
+                        let metadata = manifest_metadata(manifest_path, &self.environment)?;
+                        self.scope
+                            .selected_packages(&metadata)
+                            .into_iter()
+                            .map(|package| package.name.clone())
+                            .collect()
+                    }
                     ScopeMode::DenyList(_) =>
                         match &self.current.source {
-                            RustdocSource::Rustdoc(_) =>
+                            RustdocSource::Rustdoc(_) | RustdocSource::RemoteUrl(_) =>
                                 // This is a user-facing string.
                                 // For example, it appears when two pre-generated rustdoc files
                                 // are semver-checked against each other.
@@ -344,7 +987,8 @@ impl Check {
                     .into_iter()
                     .map(|name| {
                         let version = None;
-                        let (current_crate, baseline_crate) = generate_versioned_crates(
+                        check_deadline(deadline)?;
+                        let crates = self.generate_versioned_crates(
                             &mut config,
                             &rustdoc_cmd,
                             &*current_loader,
@@ -352,65 +996,116 @@ impl Check {
                             &name,
                             version,
                         )?;
+                        if skip_unchanged && crates.api_unchanged()? {
+                            config.shell_status("Skipping", format_args!("{name} (unchanged)"))?;
+                            unchanged_crates += 1;
+                            return Ok((name, None));
+                        }
 
+                        let broken_doc_links = self.broken_doc_links(&crates)?;
                         let report = run_check_release(
                             &mut config,
                             &name,
-                            current_crate,
-                            baseline_crate,
+                            crates.current,
+                            crates.baseline,
                             self.release_type,
+                            LintOptions {
+                                broken_doc_links,
+                                ..self.lint_options()
+                            },
+                            &mut reporter,
                         )?;
                         Ok((name, Some(report)))
                     })
                     .collect()
             }
             RustdocSource::Root(project_root) => {
-                let metadata = manifest_metadata(project_root)?;
+                let discovery_root = self.scope.manifest_path().unwrap_or(project_root);
+                let metadata = manifest_metadata(discovery_root, &self.environment)?;
                 let selected = self.scope.selected_packages(&metadata);
-                selected
-                    .iter()
-                    .map(|selected| {
-                        let crate_name = &selected.name;
-                        let version = &selected.version;
-
-                        // If the manifest we're using points to a workspace, then
-                        // ignore `publish = false` crates unless they are specifically selected.
-                        // If the manifest points to a specific crate, then check the crate
-                        // even if `publish = false` is set.
-                        let is_implied = matches!(self.scope.mode, ScopeMode::DenyList(..))
-                            && metadata.workspace_members.len() > 1
-                            && selected.publish == Some(vec![]);
-                        if is_implied {
-                            config.verbose(|config| {
-                                config.shell_status(
-                                    "Skipping",
-                                    format_args!("{crate_name} v{version} (current)"),
-                                )
-                            })?;
-                            Ok((crate_name.clone(), None))
-                        } else {
-                            let (current_crate, baseline_crate) = generate_versioned_crates(
-                                &mut config,
-                                &rustdoc_cmd,
-                                &*current_loader,
-                                &*baseline_loader,
-                                crate_name,
-                                Some(version),
-                            )?;
-
-                            Ok((
-                                crate_name.clone(),
-                                Some(run_check_release(
-                                    &mut config,
-                                    crate_name,
-                                    current_crate,
-                                    baseline_crate,
-                                    self.release_type,
-                                )?),
-                            ))
+
+                // Packages are checked in parallel, from generating their rustdoc,
+                // the slowest step, to running the lints. The messages and reporter events
+                // of each package are held back, then passed on as soon as the package
+                // and all the ones selected before it are done, so that the results
+                // are reported in a consistent order without waiting for the whole workspace.
+                let (sender, receiver) = std::sync::mpsc::channel();
+                std::thread::scope(|scope| {
+                    scope.spawn(|| {
+                        selected.par_iter().enumerate().for_each_with(
+                            sender,
+                            |sender, (index, selected)| {
+                                let mut package = PackageOutcome {
+                                    config: config.buffered(),
+                                    reporter: BufferedReporter::default(),
+                                    outcome: Ok((None, false)),
+                                };
+                                let config = &mut package.config;
+                                package.outcome = check_deadline(deadline)
+                                    .and_then(|()| {
+                                        self.generate_selected_crates(
+                                            config,
+                                            &rustdoc_cmd,
+                                            &*current_loader,
+                                            &*baseline_loader,
+                                            &metadata,
+                                            selected,
+                                        )
+                                    })
+                                    .and_then(|crates| {
+                                        let Some(crates) = crates else {
+                                            return Ok((None, false));
+                                        };
+                                        if skip_unchanged && crates.api_unchanged()? {
+                                            config.shell_status(
+                                                "Skipping",
+                                                format_args!(
+                                                    "{} v{} (unchanged)",
+                                                    selected.name, selected.version
+                                                ),
+                                            )?;
+                                            return Ok((None, true));
+                                        }
+
+                                        let broken_doc_links = self.broken_doc_links(&crates)?;
+                                        let report = run_check_release(
+                                            config,
+                                            &selected.name,
+                                            crates.current,
+                                            crates.baseline,
+                                            self.release_type,
+                                            LintOptions {
+                                                broken_doc_links,
+                                                ..self.lint_options()
+                                            },
+                                            &mut package.reporter,
+                                        )?;
+                                        Ok((Some(report), false))
+                                    });
+                                // The receiver only goes away if the main thread panicked.
+                                let _ = sender.send((index, package));
+                            },
+                        );
+                    });
+
+                    let mut outcomes = Vec::with_capacity(selected.len());
+                    let mut pending = BTreeMap::new();
+                    for (index, package) in receiver {
+                        pending.insert(index, package);
+                        while let Some(package) = pending.remove(&outcomes.len()) {
+                            let selected = &selected[outcomes.len()];
+                            outcomes.push(package.finish(&mut reporter).map(
+                                |(report, unchanged)| {
+                                    if unchanged {
+                                        unchanged_crates += 1;
+                                    }
+                                    (selected.name.clone(), report)
+                                },
+                            ));
                         }
-                    })
-                    .collect()
+                    }
+                    outcomes
+                })
             }
         };
         let crate_reports: BTreeMap<String, CrateReport> = {
@@ -424,7 +1119,21 @@ impl Check {
             reports
         };
 
-        Ok(Report { crate_reports })
+        if crate_reports.is_empty() && unchanged_crates > 0 {
+            return Ok(None);
+        }
+
+        for (key, value) in &self.metadata {
+            config
+                .verbose(|config| config.shell_status("Metadata", format_args!("{key}={value}")))?;
+        }
+
+        let report = Report {
+            crate_reports,
+            metadata: self.metadata.clone(),
+        };
+        reporter.on_complete(&report)?;
+        Ok(Some(report))
     }
 }
 
@@ -438,12 +1147,22 @@ pub struct CrateReport {
     /// For example, if the crate contains breaking changes, this is [`Some(ReleaseType::Major)`].
     /// If no additional bump beyond the already-detected one is required, this is [`Option::None`].
     required_bump: Option<ReleaseType>,
+    /// Semver violations found in the crate, grouped by the lint that found them.
+    violations: Vec<Violation>,
+    /// Time spent running the lints and collecting their results.
+    duration: std::time::Duration,
+    /// Whether any violation fails the check. See [`Check::with_pedantic()`].
+    pedantic: bool,
 }
 
 impl CrateReport {
     /// Check if the semver check was successful.
     /// `true` if required bump <= detected bump.
+    /// In pedantic mode, `true` only if there are no violations at all.
     pub fn success(&self) -> bool {
+        if self.pedantic && !self.violations.is_empty() {
+            return false;
+        }
         match self.required_bump {
             // If `None`, no additional bump is required.
             None => true,
@@ -483,6 +1202,114 @@ impl CrateReport {
     pub fn detected_bump(&self) -> ActualSemverUpdate {
         self.detected_bump
     }
+
+    /// Semver violations found in the crate, grouped by the lint that found them.
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    /// Describes each failed lint as a `(title, body)` pair of plain strings,
+    /// so that violations can be turned into annotations for any CI system.
+    ///
+    /// There is one pair per failed lint, in the same order as [`CrateReport::violations()`].
+    pub fn as_annotation_pairs(&self) -> Vec<(String, String)> {
+        self.violations
+            .iter()
+            .group_by(|violation| violation.query_id())
+            .into_iter()
+            .map(|(_, mut violations)| {
+                let first = violations.next().expect("groups are never empty");
+                let query = first.query();
+                let kind = match first.lint_level() {
+                    LintLevel::Deny => "violation",
+                    LintLevel::Warn => "warning",
+                    LintLevel::Info | LintLevel::Allow => "note",
+                };
+                let title = format!(
+                    "semver {kind} in {}: {} ({})",
+                    first.crate_name(),
+                    query.human_readable_name,
+                    query.id,
+                );
+
+                let mut body = query.error_message.clone();
+                if let Some(ref_link) = query.reference_link.as_deref() {
+                    body.push_str(&format!("\nref: {ref_link}"));
+                }
+                if let Some(suggested_fix) = first.suggested_fix() {
+                    body.push_str(&format!("\nsuggested fix: {suggested_fix}"));
+                }
+                body.push_str("\n\nFailed in:");
+                for violation in std::iter::once(first).chain(violations) {
+                    let description = match violation.message() {
+                        Some(message) => message.to_string(),
+                        None => serde_json::to_string(violation.values()).expect("serde failed"),
+                    };
+                    body.push_str(&format!("\n  {description}"));
+                }
+
+                (title, body)
+            })
+            .collect()
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let detected_bump = match self.detected_bump {
+            ActualSemverUpdate::Major => "major",
+            ActualSemverUpdate::Minor => "minor",
+            ActualSemverUpdate::Patch => "patch",
+            ActualSemverUpdate::NotChanged => "none",
+        };
+        let required_bump = self.required_bump.map(|bump| match bump {
+            ReleaseType::Major => "major",
+            ReleaseType::Minor => "minor",
+            ReleaseType::Patch => "patch",
+        });
+        let violations: Vec<_> = self
+            .violations
+            .iter()
+            .map(|violation| {
+                let values = violation.values();
+                let span = match (values.get("span_filename"), values.get("span_begin_line")) {
+                    (Some(filename), Some(begin_line)) => serde_json::json!({
+                        "filename": filename,
+                        "begin_line": begin_line,
+                    }),
+                    _ => serde_json::Value::Null,
+                };
+                serde_json::json!({
+                    "query_id": violation.query_id(),
+                    "lint_level": violation.lint_level().as_str(),
+                    "required_update": violation.required_update().as_str(),
+                    "message": violation.message(),
+                    "item_path": violation.item_path(),
+                    "span": span,
+                    "values": values,
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "success": self.success(),
+            "detected_bump": detected_bump,
+            "required_bump": required_bump,
+            "violations": violations,
+        })
+    }
+
+    /// Time spent running the lints and collecting their results.
+    pub(crate) fn duration(&self) -> std::time::Duration {
+        self.duration
+    }
+
+    /// The required update of each denied lint that found at least one violation.
+    pub(crate) fn failed_queries(&self) -> Vec<RequiredSemverUpdate> {
+        self.violations
+            .iter()
+            .filter(|violation| violation.lint_level() == LintLevel::Deny)
+            .dedup_by(|a, b| a.query_id() == b.query_id())
+            .map(Violation::required_update)
+            .collect()
+    }
 }
 
 /// Report of the whole analysis.
@@ -492,6 +1319,8 @@ impl CrateReport {
 pub struct Report {
     /// Collection containing the name and the report of each crate checked.
     crate_reports: BTreeMap<String, CrateReport>,
+    /// Key-value pairs recorded with [`Check::with_metadata()`].
+    metadata: BTreeMap<String, String>,
 }
 
 impl Report {
@@ -504,87 +1333,450 @@ impl Report {
     pub fn crate_reports(&self) -> &BTreeMap<String, CrateReport> {
         &self.crate_reports
     }
+
+    /// Semver violations found in all crates, in the order of [`Report::crate_reports()`].
+    pub fn violations(&self) -> impl Iterator<Item = &Violation> {
+        self.crate_reports
+            .values()
+            .flat_map(|report| report.violations())
+    }
+
+    /// Metadata recorded with [`Check::with_metadata()`], sorted by key.
+    pub fn metadata(&self) -> &BTreeMap<String, String> {
+        &self.metadata
+    }
+
+    /// The report as JSON, for tools that consume the results programmatically.
+    ///
+    /// The object has the overall `success`, the `metadata`, and a `crates` object
+    /// with the report of each crate by name. Each crate report has its `success`,
+    /// `detected_bump`, `required_bump` (`null` if none) and `violations`.
+    /// Each violation has its `query_id`, `lint_level`, `required_update`, `message`,
+    /// `item_path` and `span` (`null` if unknown), and the raw `values` output by the lint.
+    pub fn to_json(&self) -> serde_json::Value {
+        let crates: serde_json::Map<String, serde_json::Value> = self
+            .crate_reports
+            .iter()
+            .map(|(crate_name, report)| (crate_name.clone(), report.to_json()))
+            .collect();
+        serde_json::json!({
+            "success": self.success(),
+            "metadata": self.metadata,
+            "crates": crates,
+        })
+    }
+
+    /// The report as a SARIF 2.1.0 document, for code scanning tools
+    /// that annotate source files, e.g. GitHub code scanning.
+    ///
+    /// Each lint that found violations is a rule, and each violation is a result,
+    /// located at the source file and line reported by the lint when known.
+    /// File paths under the current directory are made relative to it.
+    pub fn to_sarif(&self) -> serde_json::Value {
+        let current_dir = std::env::current_dir().ok();
+        let mut rules = Vec::new();
+        let mut results = Vec::new();
+        for violation in self.violations() {
+            let query = violation.query();
+            if !rules
+                .iter()
+                .any(|rule: &serde_json::Value| rule["id"] == query.id.as_str())
+            {
+                rules.push(serde_json::json!({
+                    "id": query.id,
+                    "name": query.human_readable_name,
+                    "shortDescription": { "text": query.description },
+                    "fullDescription": { "text": query.error_message },
+                    "helpUri": query.reference_link,
+                }));
+            }
+
+            let level = match (violation.fails_check(), violation.lint_level()) {
+                (true, _) => "error",
+                (false, LintLevel::Warn) => "warning",
+                (false, _) => "note",
+            };
+            let text = match violation.message() {
+                Some(message) => format!("{}: {message}", query.human_readable_name),
+                None => query.human_readable_name.clone(),
+            };
+            let values = violation.values();
+            let locations = match (values.get("span_filename"), values.get("span_begin_line")) {
+                (
+                    Some(trustfall::TransparentValue::String(filename)),
+                    Some(trustfall::TransparentValue::Uint64(begin_line)),
+                ) => {
+                    let path = std::path::Path::new(filename.as_str());
+                    let path = current_dir
+                        .as_deref()
+                        .and_then(|dir| path.strip_prefix(dir).ok())
+                        .unwrap_or(path);
+                    vec![serde_json::json!({
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": path.to_string_lossy().replace('\\', "/") },
+                            "region": { "startLine": begin_line },
+                        },
+                    })]
+                }
+                _ => vec![],
+            };
+            results.push(serde_json::json!({
+                "ruleId": query.id,
+                "level": level,
+                "message": { "text": text },
+                "locations": locations,
+            }));
+        }
+
+        serde_json::json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "cargo-semver-checks",
+                        "version": clap::crate_version!(),
+                        "informationUri": "https://github.com/obi1kenobi/cargo-semver-checks",
+                        "rules": rules,
+                    },
+                },
+                "results": results,
+            }],
+        })
+    }
+
+    /// Write a Markdown migration guide skeleton for downstream users,
+    /// listing the breaking changes found in each crate.
+    ///
+    /// Only violations of [`LintLevel::Deny`] lints are listed. The guide can't know
+    /// how downstream code should be updated, so each change has a placeholder
+    /// for the crate authors to fill in.
+    pub fn write_migration_guide<W: std::io::Write>(&self, mut w: W) -> anyhow::Result<()> {
+        writeln!(w, "# Migration guide")?;
+        let breaking_crates: Vec<_> = self
+            .crate_reports
+            .iter()
+            .filter(|(_, report)| !report.failed_queries().is_empty())
+            .collect();
+        if breaking_crates.is_empty() {
+            writeln!(w)?;
+            writeln!(w, "No breaking changes were found.")?;
+        }
+
+        for (crate_name, report) in breaking_crates {
+            writeln!(w)?;
+            writeln!(w, "## `{crate_name}`")?;
+            let groups = report
+                .violations
+                .iter()
+                .filter(|violation| violation.lint_level() == LintLevel::Deny)
+                .group_by(|violation| violation.query_id());
+            for (_, violations) in &groups {
+                let violations: Vec<_> = violations.collect();
+                let query = violations[0].query();
+                writeln!(w)?;
+                writeln!(w, "### {}", query.human_readable_name)?;
+                writeln!(w)?;
+                writeln!(w, "{}", query.error_message)?;
+                writeln!(w)?;
+                for violation in &violations {
+                    let description = match (violation.message(), violation.item_path()) {
+                        (Some(message), _) => message.to_string(),
+                        (None, Some(path)) => format!("`{path}`"),
+                        (None, None) => serde_json::to_string(violation.values())?,
+                    };
+                    writeln!(w, "- {description}")?;
+                }
+                // The lints' suggested fixes are advice for the crate authors,
+                // not for downstream users, so they don't belong here.
+                writeln!(w)?;
+                writeln!(w, "**How to update:** TODO")?;
+                if let Some(link) = query.reference_link.as_deref() {
+                    writeln!(w)?;
+                    writeln!(w, "See also {link}")?;
+                }
+            }
+        }
+        w.flush()?;
+        Ok(())
+    }
 }
 
-fn generate_versioned_crates(
-    config: &mut GlobalConfig,
-    rustdoc_cmd: &RustdocCommand,
-    current_loader: &dyn rustdoc_gen::RustdocGenerator,
-    baseline_loader: &dyn rustdoc_gen::RustdocGenerator,
-    crate_name: &str,
-    version: Option<&Version>,
-) -> anyhow::Result<(VersionedCrate, VersionedCrate)> {
-    let current_path = current_loader.load_rustdoc(
-        config,
-        rustdoc_cmd,
-        rustdoc_gen::CrateDataForRustdoc {
-            name: crate_name,
-            crate_type: rustdoc_gen::CrateType::Current,
-        },
-    )?;
-    let current_crate = load_rustdoc(&current_path)?;
-
-    let current_rustdoc_version = current_crate.version();
-
-    let baseline_path =
-        get_baseline_rustdoc_path(config, rustdoc_cmd, baseline_loader, crate_name, version)?;
-    let baseline_crate = {
-        let mut baseline_crate = load_rustdoc(&baseline_path)?;
-
-        // The baseline rustdoc JSON may have been cached; ensure its rustdoc version matches
-        // the version emitted by the currently-installed toolchain.
-        //
-        // The baseline and current rustdoc JSONs should have the same version.
-        // If the baseline rustdoc version doesn't match, delete the cached baseline and rebuild it.
-        //
-        // Fix for: https://github.com/obi1kenobi/cargo-semver-checks/issues/415
-        if baseline_crate.version() != current_rustdoc_version {
+impl Check {
+    /// The documentation links broken between the baseline and current rustdoc,
+    /// if they are checked at all.
+    fn broken_doc_links(&self, crates: &GeneratedCrates) -> anyhow::Result<Vec<BrokenDocLink>> {
+        if !self.check_doc_links {
+            return Ok(vec![]);
+        }
+        let baseline = RustdocCrate::from_path(&crates.baseline_path)?;
+        let current = RustdocCrate::from_path(&crates.current_path)?;
+        Ok(RustdocDiff::compute(&baseline, &current)
+            .broken_doc_links()
+            .to_vec())
+    }
+
+    /// Generate the current and baseline rustdoc of a package selected from a project root,
+    /// or return `None` if the package is skipped.
+    fn generate_selected_crates(
+        &self,
+        config: &mut GlobalConfig,
+        rustdoc_cmd: &RustdocCommand,
+        current_loader: &dyn rustdoc_gen::RustdocGenerator,
+        baseline_loader: &dyn rustdoc_gen::RustdocGenerator,
+        metadata: &cargo_metadata::Metadata,
+        selected: &cargo_metadata::Package,
+    ) -> anyhow::Result<Option<GeneratedCrates>> {
+        let crate_name = &selected.name;
+        let version = &selected.version;
+
+        // If the manifest we're using points to a workspace, then
+        // ignore `publish = false` crates unless they are specifically selected.
+        // If the manifest points to a specific crate, then check the crate
+        // even if `publish = false` is set.
+        let is_implied = matches!(self.scope.mode, ScopeMode::DenyList(..))
+            && metadata.workspace_members.len() > 1
+            && selected.publish == Some(vec![]);
+        if is_implied {
+            config.verbose(|config| {
+                config.shell_status(
+                    "Skipping",
+                    format_args!("{crate_name} v{version} (current)"),
+                )
+            })?;
+            Ok(None)
+        } else if self.skip_unpublished && looks_unpublished(version) {
             config.shell_status(
-                "Removing",
-                format_args!("stale cached baseline rustdoc for {crate_name}"),
+                "Skipping",
+                format_args!("{crate_name} v{version} (unpublished)"),
             )?;
-            std::fs::remove_file(baseline_path)?;
-            let baseline_path = get_baseline_rustdoc_path(
+            Ok(None)
+        } else {
+            let crates = self.generate_versioned_crates(
                 config,
                 rustdoc_cmd,
+                current_loader,
                 baseline_loader,
                 crate_name,
-                version,
+                Some(version),
             )?;
-            baseline_crate = load_rustdoc(&baseline_path)?;
-
-            assert_eq!(
-                baseline_crate.version(),
-                current_rustdoc_version,
-                "Deleting and regenerating the baseline JSON file did not resolve the rustdoc \
-                version mismatch."
-            );
-        }
-
-        baseline_crate
-    };
-
-    Ok((current_crate, baseline_crate))
-}
-
-fn get_baseline_rustdoc_path(
-    config: &mut GlobalConfig,
-    rustdoc_cmd: &RustdocCommand,
-    baseline_loader: &dyn rustdoc_gen::RustdocGenerator,
-    crate_name: &str,
-    version: Option<&Version>,
-) -> anyhow::Result<PathBuf> {
-    let baseline_path = baseline_loader.load_rustdoc(
-        config,
-        rustdoc_cmd,
-        rustdoc_gen::CrateDataForRustdoc {
-            name: crate_name,
-            crate_type: rustdoc_gen::CrateType::Baseline {
-                highest_allowed_version: version,
-            },
-        },
-    )?;
-    Ok(baseline_path)
+            Ok(Some(crates))
+        }
+    }
+
+    fn generate_versioned_crates(
+        &self,
+        config: &mut GlobalConfig,
+        rustdoc_cmd: &RustdocCommand,
+        current_loader: &dyn rustdoc_gen::RustdocGenerator,
+        baseline_loader: &dyn rustdoc_gen::RustdocGenerator,
+        crate_name: &str,
+        version: Option<&Version>,
+    ) -> anyhow::Result<GeneratedCrates> {
+        let current_path = current_loader
+            .load_rustdoc(
+                config,
+                rustdoc_cmd,
+                rustdoc_gen::CrateDataForRustdoc {
+                    name: crate_name,
+                    crate_type: rustdoc_gen::CrateType::Current,
+                    features: self
+                        .features_override
+                        .as_ref()
+                        .map_or(rustdoc_gen::FeaturesToEnable::All, FeatureOverride::current),
+                    build_std: self.current_build_std,
+                    docker: None,
+                },
+            )
+            .map_err(CheckError::Rustdoc)?;
+        let current_crate = load_rustdoc(&current_path).map_err(CheckError::Rustdoc)?;
+
+        let current_rustdoc_version = current_crate.version();
+
+        let mut baseline_path = self.get_baseline_rustdoc_path(
+            config,
+            rustdoc_cmd,
+            baseline_loader,
+            crate_name,
+            version,
+        )?;
+        let baseline_crate = {
+            let mut baseline_crate = load_rustdoc(&baseline_path).map_err(CheckError::Baseline)?;
+
+            // The baseline rustdoc JSON may have been cached; ensure its rustdoc version matches
+            // the version emitted by the currently-installed toolchain.
+            //
+            // The baseline and current rustdoc JSONs should have the same version.
+            // If the baseline rustdoc version doesn't match, delete the cached baseline and rebuild it.
+            //
+            // Fix for: https://github.com/obi1kenobi/cargo-semver-checks/issues/415
+            if baseline_crate.version() != current_rustdoc_version {
+                config.shell_status(
+                    "Removing",
+                    format_args!("stale cached baseline rustdoc for {crate_name}"),
+                )?;
+                std::fs::remove_file(&baseline_path)?;
+                baseline_path = self.get_baseline_rustdoc_path(
+                    config,
+                    rustdoc_cmd,
+                    baseline_loader,
+                    crate_name,
+                    version,
+                )?;
+                baseline_crate = load_rustdoc(&baseline_path).map_err(CheckError::Baseline)?;
+
+                if let Some(docker) = &self.baseline_docker {
+                    if baseline_crate.version() != current_rustdoc_version {
+                        return Err(CheckError::Baseline(anyhow::anyhow!(
+                            "the toolchain in Docker image {} emits rustdoc JSON format version {}, \
+                            but the host toolchain emits version {}. \
+                            Use an image with a matching toolchain.",
+                            docker.image,
+                            baseline_crate.version(),
+                            current_rustdoc_version,
+                        ))
+                        .into());
+                    }
+                }
+                assert_eq!(
+                    baseline_crate.version(),
+                    current_rustdoc_version,
+                    "Deleting and regenerating the baseline JSON file did not resolve the rustdoc \
+                    version mismatch."
+                );
+            }
+
+            baseline_crate
+        };
+
+        Ok(GeneratedCrates {
+            current: current_crate,
+            baseline: baseline_crate,
+            current_path,
+            baseline_path,
+        })
+    }
+
+    fn get_baseline_rustdoc_path(
+        &self,
+        config: &mut GlobalConfig,
+        rustdoc_cmd: &RustdocCommand,
+        baseline_loader: &dyn rustdoc_gen::RustdocGenerator,
+        crate_name: &str,
+        version: Option<&Version>,
+    ) -> anyhow::Result<PathBuf> {
+        let baseline_path = baseline_loader
+            .load_rustdoc(
+                config,
+                rustdoc_cmd,
+                rustdoc_gen::CrateDataForRustdoc {
+                    name: crate_name,
+                    crate_type: rustdoc_gen::CrateType::Baseline {
+                        highest_allowed_version: version,
+                    },
+                    features: self.features_override.as_ref().map_or(
+                        rustdoc_gen::FeaturesToEnable::All,
+                        FeatureOverride::baseline,
+                    ),
+                    build_std: self.baseline_build_std,
+                    docker: self.baseline_docker.as_ref(),
+                },
+            )
+            .map_err(CheckError::Baseline)?;
+        Ok(baseline_path)
+    }
+}
+
+/// Whether the package version suggests that it has never been published.
+fn looks_unpublished(version: &Version) -> bool {
+    *version == Version::new(0, 0, 0)
+        || version
+            .pre
+            .split('.')
+            .any(|identifier| identifier.eq_ignore_ascii_case("dev"))
+}
+
+/// Whether we appear to be running in a CI environment.
+fn is_ci() -> bool {
+    ["CI", "GITHUB_ACTIONS", "CIRCLECI"].iter().any(|var| {
+        std::env::var_os(var)
+            .map(|value| !value.is_empty() && value != "false" && value != "0")
+            .unwrap_or(false)
+    })
+}
+
+fn check_deadline(deadline: Option<Instant>) -> anyhow::Result<()> {
+    match deadline {
+        Some(deadline) if Instant::now() >= deadline => {
+            anyhow::bail!("time limit reached before all crates were checked")
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The result of checking one package in parallel with others, along with the output
+/// held back until it can be passed on in order.
+struct PackageOutcome {
+    config: GlobalConfig,
+    reporter: BufferedReporter,
+    /// The package's report if its lints ran, and whether it was skipped as unchanged.
+    outcome: anyhow::Result<(Option<CrateReport>, bool)>,
+}
+
+impl PackageOutcome {
+    /// Print the held-back output, pass the held-back reporter events on to `reporter`,
+    /// and return the outcome.
+    fn finish(
+        mut self,
+        reporter: &mut dyn Reporter,
+    ) -> anyhow::Result<(Option<CrateReport>, bool)> {
+        self.config.print_buffered()?;
+        let (report, unchanged) = self.outcome?;
+        if let Some(report) = &report {
+            self.reporter.replay(reporter, report)?;
+        }
+        Ok((report, unchanged))
+    }
+}
+
+/// The current and baseline rustdoc of a crate, along with the JSON files they were loaded from.
+struct GeneratedCrates {
+    current: VersionedCrate,
+    baseline: VersionedCrate,
+    current_path: PathBuf,
+    baseline_path: PathBuf,
+}
+
+impl GeneratedCrates {
+    /// Whether the current and baseline rustdoc describe the same API.
+    ///
+    /// The crate version and the source spans are ignored, since they differ
+    /// between otherwise-identical builds from different locations.
+    /// Any other difference, including in doc comments, counts as a change.
+    fn api_unchanged(&self) -> anyhow::Result<bool> {
+        Ok(rustdoc_api_hash(&self.current_path)? == rustdoc_api_hash(&self.baseline_path)?)
+    }
+}
+
+fn rustdoc_api_hash(rustdoc_path: &Path) -> anyhow::Result<u64> {
+    use std::hash::{Hash, Hasher};
+
+    let file = std::fs::File::open(rustdoc_path)
+        .with_context(|| format!("failed to open rustdoc JSON {}", rustdoc_path.display()))?;
+    let mut rustdoc: serde_json::Value = serde_json::from_reader(std::io::BufReader::new(file))
+        .with_context(|| format!("failed to parse rustdoc JSON {}", rustdoc_path.display()))?;
+    if let Some(rustdoc) = rustdoc.as_object_mut() {
+        rustdoc.remove("crate_version");
+        if let Some(index) = rustdoc.get_mut("index").and_then(|x| x.as_object_mut()) {
+            for item in index.values_mut().filter_map(|item| item.as_object_mut()) {
+                item.remove("span");
+            }
+        }
+    }
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    rustdoc.to_string().hash(&mut hasher);
+    Ok(hasher.finish())
 }
 
 fn manifest_path(project_root: &Path) -> anyhow::Result<PathBuf> {
@@ -614,16 +1806,30 @@ fn manifest_path(project_root: &Path) -> anyhow::Result<PathBuf> {
     }
 }
 
-fn manifest_metadata(project_root: &Path) -> anyhow::Result<cargo_metadata::Metadata> {
-    let manifest_path = manifest_path(project_root)?;
+fn metadata_command(env: &HashMap<String, String>) -> cargo_metadata::MetadataCommand {
     let mut command = cargo_metadata::MetadataCommand::new();
+    for (key, value) in env {
+        command.env(key, value);
+    }
+    command
+}
+
+fn manifest_metadata(
+    project_root: &Path,
+    env: &HashMap<String, String>,
+) -> anyhow::Result<cargo_metadata::Metadata> {
+    let manifest_path = manifest_path(project_root)?;
+    let mut command = metadata_command(env);
     let metadata = command.manifest_path(manifest_path).exec()?;
     Ok(metadata)
 }
 
-fn manifest_metadata_no_deps(project_root: &Path) -> anyhow::Result<cargo_metadata::Metadata> {
+fn manifest_metadata_no_deps(
+    project_root: &Path,
+    env: &HashMap<String, String>,
+) -> anyhow::Result<cargo_metadata::Metadata> {
     let manifest_path = manifest_path(project_root)?;
-    let mut command = cargo_metadata::MetadataCommand::new();
+    let mut command = metadata_command(env);
     let metadata = command.manifest_path(manifest_path).no_deps().exec()?;
     Ok(metadata)
 }
@@ -636,20 +1842,27 @@ fn get_cache_dir() -> anyhow::Result<PathBuf> {
     Ok(cache_dir.to_path_buf())
 }
 
-fn get_target_dir_from_project_root(source: &RustdocSource) -> anyhow::Result<Option<PathBuf>> {
+fn get_target_dir_from_project_root(
+    source: &RustdocSource,
+    env: &HashMap<String, String>,
+) -> anyhow::Result<Option<PathBuf>> {
     Ok(match source {
         RustdocSource::Root(root) => {
-            let metadata = manifest_metadata_no_deps(root)?;
+            let metadata = manifest_metadata_no_deps(root, env)?;
             let target = metadata.target_directory.as_std_path().join(util::SCOPE);
             Some(target)
         }
         RustdocSource::Revision(root, rev) => {
-            let metadata = manifest_metadata_no_deps(root)?;
+            let metadata = manifest_metadata_no_deps(root, env)?;
             let target = metadata.target_directory.as_std_path().join(util::SCOPE);
             let target = target.join(format!("git-{}", util::slugify(rev)));
             Some(target)
         }
         RustdocSource::Rustdoc(_path) => None,
+        RustdocSource::RemoteUrl(_url) => None,
+        RustdocSource::GitHub(_owner, _repo, _sha) => None,
+        RustdocSource::Archive(_path) => None,
         RustdocSource::VersionFromRegistry(_version) => None,
+        RustdocSource::VersionFromRegistryUrl(_url, _version) => None,
     })
 }
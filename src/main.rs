@@ -1,11 +1,20 @@
 #![forbid(unsafe_code)]
 
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
+use anyhow::Context;
 use cargo_semver_checks::{
-    GlobalConfig, PackageSelection, ReleaseType, Rustdoc, ScopeSelection, SemverQuery,
+    FeatureOverride, GlobalConfig, JsonReporter, JunitReporter, LintLevel, PackageSelection,
+    ReleaseType, Rustdoc, SarifReporter, ScopeSelection, SemverQuery, TerminalReporter,
 };
-use clap::{Args, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+/// Where `--fix` writes the migration guide, relative to the current directory.
+const MIGRATION_GUIDE_PATH: &str = "MIGRATION.md";
+
+/// The config file that's loaded if `--config-file` isn't given.
+const DEFAULT_CONFIG_FILE_PATH: &str = ".cargo/semver-checks.toml";
 
 fn main() -> anyhow::Result<()> {
     human_panic::setup_panic!();
@@ -22,30 +31,76 @@ fn main() -> anyhow::Result<()> {
             .print::<Markdown>();
         std::process::exit(0);
     } else if args.list {
-        let queries = SemverQuery::all_queries();
-        let mut rows = vec![["id", "type", "description"], ["==", "====", "==========="]];
-        for query in queries.values() {
-            rows.push([
-                query.id.as_str(),
-                query.required_update.as_str(),
-                query.description.as_str(),
-            ]);
+        let mut queries: Vec<_> = SemverQuery::all_queries().into_values().collect();
+        let grouped = args.group_by == Some(ListGrouping::Category);
+        if grouped {
+            // The sort is stable, so lints stay sorted by id within each group.
+            queries.sort_by_key(SemverQuery::lint_group);
+        }
+        let header = [["id", "type", "description"], ["==", "====", "==========="]];
+        let mut rows = vec![];
+        for query in &queries {
+            let kind = match query.lint_level {
+                LintLevel::Warn | LintLevel::Info => query.lint_level.as_str(),
+                _ => query.required_update.as_str(),
+            };
+            rows.push([query.id.as_str(), kind, query.description.as_str()]);
+        }
+        match args.format {
+            OutputFormat::Human => {}
+            OutputFormat::Json => {
+                let lints: Vec<_> = queries
+                    .iter()
+                    .zip(&rows)
+                    .map(|(query, row)| {
+                        serde_json::json!({
+                            "id": query.id,
+                            "type": row[1],
+                            "description": query.description,
+                            "reference": query.reference,
+                            "reference_link": query.reference_link,
+                        })
+                    })
+                    .collect();
+                use std::io::Write;
+                writeln!(
+                    std::io::stdout(),
+                    "{}",
+                    serde_json::to_string_pretty(&lints)?
+                )?;
+                std::process::exit(0);
+            }
+            OutputFormat::Junit | OutputFormat::Sarif => {
+                anyhow::bail!("`--list` can only be printed in the `human` or `json` format");
+            }
         }
         let mut widths = [0; 3];
-        for row in &rows {
+        for row in header.iter().chain(&rows) {
             widths[0] = widths[0].max(row[0].len());
             widths[1] = widths[1].max(row[1].len());
             widths[2] = widths[2].max(row[2].len());
         }
         let stdout = std::io::stdout();
         let mut stdout = stdout.lock();
-        for row in rows {
+        let mut print_row = |row: &[&str; 3]| {
             use std::io::Write;
-            writeln!(
-                stdout,
+            let line = format!(
                 "{0:<1$} {2:<3$} {4:<5$}",
                 row[0], widths[0], row[1], widths[1], row[2], widths[2]
-            )?;
+            );
+            writeln!(stdout, "{}", line.trim_end())
+        };
+        for row in &header {
+            print_row(row)?;
+        }
+        let mut current_group = None;
+        for (query, row) in queries.iter().zip(&rows) {
+            if grouped && current_group != Some(query.lint_group()) {
+                current_group = Some(query.lint_group());
+                print_row(&["", "", ""])?;
+                print_row(&[&format!("[{}]", query.lint_group()), "", ""])?;
+            }
+            print_row(row)?;
         }
 
         let mut config = GlobalConfig::new().set_level(args.verbosity.log_level());
@@ -61,25 +116,87 @@ fn main() -> anyhow::Result<()> {
                 ids.join("\n  ")
             )
         })?;
-        println!(
-            "{}",
-            query
-                .reference
-                .as_deref()
-                .unwrap_or(query.description.as_str())
-        );
-        if let Some(link) = &query.reference_link {
-            println!();
-            println!("See also {link}");
-        }
+        println!("{}", query.explanation());
+        std::process::exit(0);
+    } else if let Some(path) = args.validate_query_file.as_deref() {
+        SemverQuery::validate_ron_file(path)?;
+
+        let mut config = GlobalConfig::new().set_level(args.verbosity.log_level());
+        config.shell_status("Validated", path.display())?;
         std::process::exit(0);
     }
 
     match args.command {
-        Some(SemverChecksCommands::CheckRelease(args)) => {
-            let check: cargo_semver_checks::Check = args.into();
-            let report = check.check_release()?;
-            if report.success() {
+        Some(SemverChecksCommands::CheckRelease(mut args)) => {
+            let config_file_path = args.config_file.clone().or_else(|| {
+                let path = PathBuf::from(DEFAULT_CONFIG_FILE_PATH);
+                path.is_file().then_some(path)
+            });
+            let mut config_file = config_file_path
+                .as_deref()
+                .map(read_config_file)
+                .transpose()?
+                .unwrap_or_default();
+            let warnings_only = std::mem::take(&mut config_file.warn);
+            let environment = config_file.apply(&mut args)?;
+            let report_format = args.report_format;
+            let explain_violations = args.explain_violations;
+            let fix = args.fix;
+            let log_level = args.verbosity.log_level();
+            let feature_combinations = args
+                .feature_combinations
+                .as_deref()
+                .map(read_feature_combinations)
+                .transpose()?;
+            let mut check: cargo_semver_checks::Check = args.into();
+            if !environment.is_empty() {
+                check.with_environment(environment);
+            }
+            if !warnings_only.is_empty() {
+                check.with_warnings_only(warnings_only);
+            }
+
+            let success = if let Some(combinations) = feature_combinations {
+                let mut config = GlobalConfig::new().set_level(log_level);
+                let mut success = true;
+                for combination in combinations {
+                    config.shell_status("Features", describe_features(&combination))?;
+                    let features_override = FeatureOverride::from(combination);
+                    let report = check
+                        .with_features_override(features_override)
+                        .check_release()?;
+                    success &= report.success();
+                }
+                success
+            } else {
+                let report =
+                    match report_format {
+                        OutputFormat::Human if explain_violations => {
+                            let config = GlobalConfig::new().set_level(log_level);
+                            let reporter = TerminalReporter::new(config).with_explanations(true);
+                            check.check_release_with_reporter(reporter)?
+                        }
+                        OutputFormat::Human => check.check_release()?,
+                        OutputFormat::Junit => check
+                            .check_release_with_reporter(JunitReporter::new(std::io::stdout()))?,
+                        OutputFormat::Json => check
+                            .check_release_with_reporter(JsonReporter::new(std::io::stdout()))?,
+                        OutputFormat::Sarif => check
+                            .check_release_with_reporter(SarifReporter::new(std::io::stdout()))?,
+                    };
+                if fix {
+                    let path = Path::new(MIGRATION_GUIDE_PATH);
+                    let file = std::fs::File::create(path)
+                        .with_context(|| format!("failed to create {}", path.display()))?;
+                    report.write_migration_guide(std::io::BufWriter::new(file))?;
+                    GlobalConfig::new().set_level(log_level).shell_status(
+                        "Wrote",
+                        format_args!("migration guide to {}", path.display()),
+                    )?;
+                }
+                report.success()
+            };
+            if success {
                 std::process::exit(0)
             } else {
                 std::process::exit(1);
@@ -108,9 +225,30 @@ struct SemverChecks {
     #[arg(long, global = true, exclusive = true)]
     explain: Option<String>,
 
-    #[arg(long, global = true, exclusive = true)]
+    // Not global, so that `args_conflicts_with_subcommands` also rejects it
+    // after the subcommand, where a global argument would be accepted.
+    #[arg(long, conflicts_with = "explain")]
     list: bool,
 
+    /// Group the lints printed by `--list`.
+    #[arg(long, value_name = "KEY", requires = "list")]
+    group_by: Option<ListGrouping>,
+
+    /// Format in which to print the lints listed by `--list`.
+    #[arg(
+        value_enum,
+        long,
+        value_name = "FORMAT",
+        default_value_t = OutputFormat::Human,
+        requires = "list"
+    )]
+    format: OutputFormat,
+
+    /// Check that a query definition file is well-formed, without running it.
+    /// The query is checked against the schema of rustdoc format v24 only.
+    #[arg(long, global = true, exclusive = true, value_name = "RON_PATH")]
+    validate_query_file: Option<PathBuf>,
+
     #[command(flatten)]
     verbosity: clap_verbosity_flag::Verbosity<clap_verbosity_flag::InfoLevel>,
 
@@ -133,6 +271,11 @@ struct CheckRelease {
     #[command(flatten, next_help_heading = "Current")]
     pub workspace: clap_cargo::Workspace,
 
+    /// Skip packages whose version suggests they have never been published,
+    /// such as `0.0.0` or `0.1.0-dev`, instead of failing to find their baseline.
+    #[arg(long, help_heading = "Current")]
+    skip_unpublished: bool,
+
     /// The current rustdoc json output to test for semver violations.
     #[arg(
         long,
@@ -144,6 +287,12 @@ struct CheckRelease {
     )]
     current_rustdoc: Option<PathBuf>,
 
+    /// Build the standard library from source when generating the current rustdoc,
+    /// by passing `-Z build-std` to cargo. Requires a nightly-capable toolchain
+    /// with the `rust-src` component, and an explicit target such as `CARGO_BUILD_TARGET`.
+    #[arg(long, help_heading = "Current")]
+    current_build_std: bool,
+
     /// Version from registry to lookup for a baseline
     #[arg(
         long,
@@ -153,6 +302,15 @@ struct CheckRelease {
     )]
     baseline_version: Option<String>,
 
+    /// Git index URL of the registry to lookup `--baseline-version` in, instead of crates.io
+    #[arg(
+        long,
+        value_name = "URL",
+        help_heading = "Baseline",
+        requires = "baseline_version"
+    )]
+    baseline_registry_url: Option<String>,
+
     /// Git revision to lookup for a baseline
     #[arg(
         long,
@@ -162,6 +320,27 @@ struct CheckRelease {
     )]
     baseline_rev: Option<String>,
 
+    /// GitHub repository commit to download the baseline crate source from,
+    /// without a local clone. Uses `GITHUB_TOKEN` to authenticate, if it's set.
+    #[arg(
+        long,
+        value_name = "OWNER/REPO@SHA",
+        help_heading = "Baseline",
+        group = "baseline",
+        value_parser = parse_github_commit
+    )]
+    baseline_github: Option<(String, String, String)>,
+
+    /// `.zip` or `.tar.gz` archive of the baseline crate source, extracted with `tar`.
+    /// Reading `.zip` archives needs bsdtar, the `tar` of Windows and macOS.
+    #[arg(
+        long,
+        value_name = "ARCHIVE_PATH",
+        help_heading = "Baseline",
+        group = "baseline"
+    )]
+    baseline_archive: Option<PathBuf>,
+
     /// Directory containing baseline crate source
     #[arg(
         long,
@@ -182,6 +361,48 @@ struct CheckRelease {
     )]
     baseline_rustdoc: Option<PathBuf>,
 
+    /// Build the standard library from source when generating the baseline rustdoc,
+    /// by passing `-Z build-std` to cargo. See `--current-build-std`.
+    #[arg(long, help_heading = "Baseline")]
+    baseline_build_std: bool,
+
+    /// Regenerate the baseline rustdoc instead of reusing the copy cached
+    /// by a previous run in `target/semver-checks/cache/`.
+    #[arg(long, help_heading = "Baseline")]
+    no_cache: bool,
+
+    /// Generate the baseline rustdoc in a container of this Docker image,
+    /// for a build that doesn't depend on the host's toolchain and configuration.
+    /// The image's toolchain must emit the same rustdoc JSON format as the host's.
+    #[arg(long, value_name = "IMAGE", help_heading = "Baseline")]
+    baseline_docker_image: Option<String>,
+
+    /// Host directory to use as the container's cargo home with `--baseline-docker-image`,
+    /// so that downloaded crates are reused between runs.
+    #[arg(
+        long,
+        value_name = "DIR",
+        help_heading = "Baseline",
+        requires = "baseline_docker_image"
+    )]
+    baseline_docker_cargo_home: Option<PathBuf>,
+
+    /// Generate the current and baseline rustdoc for the custom target
+    /// described by this target spec JSON file, e.g. for embedded platforms.
+    /// Usually combined with `--current-build-std` and `--baseline-build-std`.
+    #[arg(long, value_name = "FILE")]
+    target_spec_json: Option<PathBuf>,
+
+    /// Replace the crates.io dependency `NAME` with the crate in directory `PATH`
+    /// in both the current and baseline builds, like a `[patch.crates-io]` entry.
+    /// Can be repeated.
+    #[arg(
+        long = "dependency-override",
+        value_name = "NAME=PATH",
+        value_parser = parse_dependency_override
+    )]
+    dependency_overrides: Vec<(String, PathBuf)>,
+
     /// Sets the release type instead of deriving it from the version number.
     #[arg(
         value_enum,
@@ -192,10 +413,173 @@ struct CheckRelease {
     )]
     release_type: Option<ReleaseType>,
 
+    /// Assume a patch release regardless of the version change: run every deny-level lint,
+    /// and fail if any of them finds a violation. Warn-level lints still only warn;
+    /// add `--pedantic` to fail on those too. All lints still run on all packages
+    /// before the results are reported.
+    #[arg(long, help_heading = "Overrides", group = "overrides")]
+    assume_no_break: bool,
+
+    /// Print the full `--explain` text of each lint that found violations,
+    /// along with the violations themselves.
+    #[arg(long, help_heading = "Report")]
+    explain_violations: bool,
+
+    /// Fail the check on any violation, including warnings and informational
+    /// ones that normally never affect the result.
+    #[arg(long, help_heading = "Report")]
+    pedantic: bool,
+
+    /// Write a MIGRATION.md skeleton in the current directory, listing the breaking
+    /// changes found for downstream users. Existing content is overwritten.
+    #[arg(long, help_heading = "Report", conflicts_with = "feature_combinations")]
+    fix: bool,
+
+    /// Also list public items whose documentation changed.
+    /// Documentation changes are informational, and never fail the check.
+    #[arg(long, help_heading = "Report")]
+    compare_docs: bool,
+
+    /// Warn about documentation links that no longer resolve, in items that are still present.
+    /// Broken links don't break downstream code, so they only fail the check with `--pedantic`.
+    #[arg(long, help_heading = "Report")]
+    check_doc_links: bool,
+
+    /// Add a `KEY=VALUE` pair to the report's metadata, e.g. for audit purposes.
+    /// The tool doesn't interpret these; they are only recorded. Can be repeated.
+    #[arg(
+        long = "compare-flag",
+        value_name = "KEY=VALUE",
+        value_parser = parse_compare_flag,
+        help_heading = "Report"
+    )]
+    compare_flags: Vec<(String, String)>,
+
+    /// Comma-separated list of features to enable, together with the default features,
+    /// in both the current and baseline versions instead of all features.
+    #[arg(
+        long,
+        value_name = "FEATURES",
+        value_delimiter = ',',
+        help_heading = "Features",
+        conflicts_with = "all_features"
+    )]
+    features: Vec<String>,
+
+    /// Don't enable the default features in the current and baseline versions.
+    /// Only the features passed with `--features` are enabled, if any.
+    #[arg(long, help_heading = "Features", conflicts_with = "all_features")]
+    no_default_features: bool,
+
+    /// Enable all features in both the current and baseline versions. This is the default.
+    #[arg(long, help_heading = "Features")]
+    all_features: bool,
+
+    /// Disable the lints with these ids.
+    #[arg(
+        long,
+        value_name = "LINT",
+        value_delimiter = ',',
+        help_heading = "Lints"
+    )]
+    allow: Vec<String>,
+
+    /// Report violations of the lints with these ids without failing the check.
+    /// Takes precedence over `--allow`.
+    #[arg(
+        long,
+        value_name = "LINT",
+        value_delimiter = ',',
+        help_heading = "Lints"
+    )]
+    warn: Vec<String>,
+
+    /// Fail the check on violations of the lints with these ids.
+    /// Takes precedence over `--allow` and `--warn`.
+    #[arg(
+        long,
+        value_name = "LINT",
+        value_delimiter = ',',
+        help_heading = "Lints"
+    )]
+    deny: Vec<String>,
+
+    /// Run the check once for each feature combination listed in the given TOML file,
+    /// enabling the same features in the current and baseline versions.
+    ///
+    /// Each `[[combination]]` table can set `features` (a list of feature names)
+    /// and `no-default-features` (a boolean). The check fails if any combination fails.
+    #[arg(
+        long,
+        value_name = "TOML_PATH",
+        help_heading = "Features",
+        conflicts_with_all = ["report_format", "features", "all_features", "no_default_features"]
+    )]
+    feature_combinations: Option<PathBuf>,
+
+    /// Load settings from the given TOML or JSON file, e.g. an organization-wide policy.
+    /// JSON is used if the file name ends in `.json`.
+    ///
+    /// Keys are named like the options of this command, and the command line takes
+    /// precedence over the file. The file can set:
+    /// - the flags `pedantic`, `compare-docs`, `check-doc-links`, `explain-violations`,
+    ///   `skip-unpublished`, `current-build-std`, `baseline-build-std` and `no-cache`,
+    ///   which are enabled if set in either the file or on the command line;
+    /// - `features`, `no-default-features` and `all-features`, used if none of the
+    ///   feature options are given on the command line;
+    /// - `release-type` or `assume-no-break`, used if neither is given on the command line;
+    /// - one of `baseline-version` (with an optional `baseline-registry-url`), `baseline-rev`
+    ///   and `baseline-root`, used if no baseline is given on the command line;
+    /// - `baseline-docker-image`, `baseline-docker-cargo-home` and `target-spec-json`;
+    /// - `dependency-overrides`, a table of `NAME = PATH` entries like `--dependency-override`;
+    /// - `lints`, a table of lint ids and their `allow`, `warn` or `deny` level,
+    ///   like `--allow`, `--warn` and `--deny`;
+    /// - `warn`, a list of lint ids whose violations are reported as warnings, but only
+    ///   when the version change doesn't already allow them;
+    /// - `metadata`, a table of key-value pairs like `--compare-flag`;
+    /// - `environment`, a table of variables to set on the spawned cargo processes.
+    ///
+    /// Paths are relative to the current directory. The packages to check, the current
+    /// version and the report format can only be set on the command line.
+    ///
+    /// Defaults to `.cargo/semver-checks.toml` in the current directory, if it exists.
+    #[arg(long, value_name = "PATH")]
+    config_file: Option<PathBuf>,
+
+    /// Format in which to report the results on stdout.
+    #[arg(
+        value_enum,
+        long,
+        alias = "format",
+        value_name = "FORMAT",
+        default_value_t = OutputFormat::Human,
+        help_heading = "Report"
+    )]
+    report_format: OutputFormat,
+
     #[command(flatten)]
     verbosity: clap_verbosity_flag::Verbosity<clap_verbosity_flag::InfoLevel>,
 }
 
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum ListGrouping {
+    /// The kind of item the lint checks, like functions or traits.
+    Category,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    /// Human-readable output for the terminal, such as a table of lints for `--list`.
+    #[value(alias = "table")]
+    Human,
+    /// JUnit XML, for CI systems that display test results.
+    Junit,
+    /// JSON, for tools that process the results. See `Report::to_json()` in the library.
+    Json,
+    /// SARIF 2.1.0, for code scanning tools that annotate the affected source lines.
+    Sarif,
+}
+
 impl From<CheckRelease> for cargo_semver_checks::Check {
     fn from(value: CheckRelease) -> Self {
         let (current, current_project_root) = if let Some(current_rustdoc) = value.current_rustdoc {
@@ -233,7 +617,10 @@ impl From<CheckRelease> for cargo_semver_checks::Check {
         }
         let custom_baseline = {
             if let Some(baseline_version) = value.baseline_version {
-                Some(Rustdoc::from_registry(baseline_version))
+                Some(match value.baseline_registry_url {
+                    Some(url) => Rustdoc::from_registry_url(url, baseline_version),
+                    None => Rustdoc::from_registry(baseline_version),
+                })
             } else if let Some(baseline_rev) = value.baseline_rev {
                 let root = if let Some(baseline_root) = value.baseline_root {
                     baseline_root
@@ -243,6 +630,10 @@ impl From<CheckRelease> for cargo_semver_checks::Check {
                     std::env::current_dir().expect("can't determine current directory")
                 };
                 Some(Rustdoc::from_git_revision(root, baseline_rev))
+            } else if let Some((owner, repo, sha)) = value.baseline_github {
+                Some(Rustdoc::from_github(owner, repo, sha))
+            } else if let Some(baseline_archive) = value.baseline_archive {
+                Some(Rustdoc::from_archive(baseline_archive))
             } else if let Some(baseline_rustdoc) = value.baseline_rustdoc {
                 Some(Rustdoc::from_path(baseline_rustdoc))
             } else {
@@ -260,13 +651,540 @@ impl From<CheckRelease> for cargo_semver_checks::Check {
         if let Some(release_type) = value.release_type {
             check.with_release_type(release_type);
         }
+        if value.assume_no_break {
+            check.with_assume_no_break();
+        }
+        if !value.features.is_empty() {
+            check.with_feature_flags(value.features);
+        }
+        if value.no_default_features {
+            check.with_default_features(false);
+        }
+        if value.current_build_std {
+            check.with_current_build_std();
+        }
+        if value.baseline_build_std {
+            check.with_baseline_build_std();
+        }
+        if value.no_cache {
+            check.with_no_cache();
+        }
+        if let Some(image) = value.baseline_docker_image {
+            check.with_baseline_docker(image, value.baseline_docker_cargo_home);
+        }
+        for (ids, level) in [
+            (value.allow, LintLevel::Allow),
+            (value.warn, LintLevel::Warn),
+            (value.deny, LintLevel::Deny),
+        ] {
+            for id in ids {
+                check.with_lint_level(id, level);
+            }
+        }
+        if let Some(target_spec) = value.target_spec_json {
+            check.with_target_spec_json(target_spec);
+        }
+        if !value.dependency_overrides.is_empty() {
+            check.with_dependency_overrides(value.dependency_overrides);
+        }
+        if value.compare_docs {
+            check.with_compare_docs();
+        }
+        if value.check_doc_links {
+            check.with_check_doc_links();
+        }
+        if value.pedantic {
+            check.with_pedantic();
+        }
+        if value.skip_unpublished {
+            check.with_skip_unpublished();
+        }
+        for (key, value) in value.compare_flags {
+            check.with_metadata(key, value);
+        }
 
         check
     }
 }
 
+/// The contents of a `--config-file` file.
+#[derive(Debug, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct ConfigFile {
+    #[serde(default)]
+    pedantic: bool,
+    #[serde(default)]
+    compare_docs: bool,
+    #[serde(default)]
+    check_doc_links: bool,
+    #[serde(default)]
+    explain_violations: bool,
+    #[serde(default)]
+    skip_unpublished: bool,
+    #[serde(default)]
+    current_build_std: bool,
+    #[serde(default)]
+    baseline_build_std: bool,
+    #[serde(default)]
+    no_cache: bool,
+    #[serde(default)]
+    features: Vec<String>,
+    #[serde(default)]
+    no_default_features: bool,
+    #[serde(default)]
+    all_features: bool,
+    release_type: Option<String>,
+    #[serde(default)]
+    assume_no_break: bool,
+    baseline_version: Option<String>,
+    baseline_registry_url: Option<String>,
+    baseline_rev: Option<String>,
+    baseline_root: Option<PathBuf>,
+    baseline_docker_image: Option<String>,
+    baseline_docker_cargo_home: Option<PathBuf>,
+    target_spec_json: Option<PathBuf>,
+    #[serde(default)]
+    dependency_overrides: BTreeMap<String, PathBuf>,
+    #[serde(default)]
+    lints: BTreeMap<String, ConfigLintLevel>,
+    #[serde(default)]
+    metadata: BTreeMap<String, String>,
+    #[serde(default)]
+    environment: HashMap<String, String>,
+    #[serde(default)]
+    warn: Vec<String>,
+}
+
+/// A lint level in the `lints` table of a `--config-file` file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ConfigLintLevel {
+    Allow,
+    Warn,
+    Deny,
+}
+
+impl ConfigFile {
+    /// Merge the settings into the command-line arguments, which take precedence.
+    /// Returns the environment variables to set, which have no command-line equivalent.
+    fn apply(self, args: &mut CheckRelease) -> anyhow::Result<HashMap<String, String>> {
+        args.pedantic |= self.pedantic;
+        args.compare_docs |= self.compare_docs;
+        args.check_doc_links |= self.check_doc_links;
+        args.explain_violations |= self.explain_violations;
+        args.skip_unpublished |= self.skip_unpublished;
+        args.current_build_std |= self.current_build_std;
+        args.baseline_build_std |= self.baseline_build_std;
+        args.no_cache |= self.no_cache;
+
+        // Settings that choose between alternatives, like the features to enable,
+        // are only taken from the file if the command line doesn't choose any of them.
+        if self.all_features && (!self.features.is_empty() || self.no_default_features) {
+            anyhow::bail!(
+                "`all-features` can't be combined with `features` or `no-default-features`"
+            );
+        }
+        let features_chosen = !args.features.is_empty()
+            || args.no_default_features
+            || args.all_features
+            || args.feature_combinations.is_some();
+        if !features_chosen {
+            args.features = self.features;
+            args.no_default_features = self.no_default_features;
+            args.all_features = self.all_features;
+        }
+
+        if self.release_type.is_some() && self.assume_no_break {
+            anyhow::bail!("`release-type` can't be combined with `assume-no-break`");
+        }
+        if args.release_type.is_none() && !args.assume_no_break {
+            args.release_type = self
+                .release_type
+                .map(|value| ReleaseType::from_str(&value, true))
+                .transpose()
+                .map_err(|err| anyhow::anyhow!("invalid `release-type`: {err}"))?;
+            args.assume_no_break = self.assume_no_break;
+        }
+
+        let baselines = [
+            self.baseline_version.is_some(),
+            self.baseline_rev.is_some(),
+            self.baseline_root.is_some(),
+        ];
+        if baselines.into_iter().filter(|is_set| *is_set).count() > 1 {
+            anyhow::bail!(
+                "only one of `baseline-version`, `baseline-rev` and `baseline-root` can be set"
+            );
+        }
+        let baseline_chosen = args.baseline_version.is_some()
+            || args.baseline_rev.is_some()
+            || args.baseline_github.is_some()
+            || args.baseline_archive.is_some()
+            || args.baseline_root.is_some()
+            || args.baseline_rustdoc.is_some();
+        if !baseline_chosen {
+            args.baseline_version = self.baseline_version;
+            args.baseline_registry_url = self.baseline_registry_url;
+            args.baseline_rev = self.baseline_rev;
+            args.baseline_root = self.baseline_root;
+        }
+        if args.baseline_registry_url.is_some() && args.baseline_version.is_none() {
+            anyhow::bail!("`baseline-registry-url` requires `baseline-version`");
+        }
+
+        if args.baseline_docker_image.is_none() {
+            args.baseline_docker_image = self.baseline_docker_image;
+        }
+        if args.baseline_docker_cargo_home.is_none() {
+            args.baseline_docker_cargo_home = self.baseline_docker_cargo_home;
+        }
+        if args.baseline_docker_cargo_home.is_some() && args.baseline_docker_image.is_none() {
+            anyhow::bail!("`baseline-docker-cargo-home` requires `baseline-docker-image`");
+        }
+
+        if args.target_spec_json.is_none() {
+            args.target_spec_json = self.target_spec_json;
+        }
+        for (name, path) in self.dependency_overrides {
+            if !args
+                .dependency_overrides
+                .iter()
+                .any(|(arg, _)| *arg == name)
+            {
+                args.dependency_overrides.push((name, path));
+            }
+        }
+        for (id, level) in self.lints {
+            if [&args.allow, &args.warn, &args.deny]
+                .iter()
+                .any(|ids| ids.contains(&id))
+            {
+                continue;
+            }
+            match level {
+                ConfigLintLevel::Allow => args.allow.push(id),
+                ConfigLintLevel::Warn => args.warn.push(id),
+                ConfigLintLevel::Deny => args.deny.push(id),
+            }
+        }
+
+        // Metadata is recorded in order, so later command-line values win.
+        let compare_flags = std::mem::take(&mut args.compare_flags);
+        args.compare_flags = self.metadata.into_iter().chain(compare_flags).collect();
+        Ok(self.environment)
+    }
+}
+
+fn read_config_file(path: &Path) -> anyhow::Result<ConfigFile> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read config file {}", path.display()))?;
+    let config = if path.extension() == Some("json".as_ref()) {
+        serde_json::from_str(&contents).map_err(anyhow::Error::from)
+    } else {
+        toml::from_str(&contents).map_err(anyhow::Error::from)
+    };
+    config.with_context(|| format!("failed to parse config file {}", path.display()))
+}
+
+/// The contents of a `--feature-combinations` file.
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FeatureCombinations {
+    #[serde(rename = "combination", default)]
+    combinations: Vec<FeatureCombination>,
+}
+
+#[derive(Debug, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct FeatureCombination {
+    #[serde(default)]
+    features: Vec<String>,
+    #[serde(default)]
+    no_default_features: bool,
+}
+
+impl From<FeatureCombination> for FeatureOverride {
+    fn from(value: FeatureCombination) -> Self {
+        let mut features = FeatureOverride::new();
+        features
+            .with_current_features(value.features.clone())
+            .with_baseline_features(value.features)
+            .with_current_no_default(value.no_default_features)
+            .with_baseline_no_default(value.no_default_features);
+        features
+    }
+}
+
+fn read_feature_combinations(path: &Path) -> anyhow::Result<Vec<FeatureCombination>> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read feature combinations {}", path.display()))?;
+    parse_feature_combinations(&contents)
+        .with_context(|| format!("failed to parse feature combinations {}", path.display()))
+}
+
+fn parse_feature_combinations(contents: &str) -> anyhow::Result<Vec<FeatureCombination>> {
+    let file: FeatureCombinations = toml::from_str(contents)?;
+    if file.combinations.is_empty() {
+        anyhow::bail!("no `[[combination]]` entries found");
+    }
+    Ok(file.combinations)
+}
+
+fn describe_features(combination: &FeatureCombination) -> String {
+    let features = if combination.features.is_empty() {
+        "no extra features".to_string()
+    } else {
+        combination.features.join(", ")
+    };
+    if combination.no_default_features {
+        format!("{features} (without default features)")
+    } else {
+        features
+    }
+}
+
+fn parse_compare_flag(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!("expected `KEY=VALUE`, found `{value}`")),
+    }
+}
+
+fn parse_dependency_override(value: &str) -> Result<(String, PathBuf), String> {
+    match value.split_once('=') {
+        Some((name, path)) if !name.is_empty() && !path.is_empty() => {
+            Ok((name.to_string(), PathBuf::from(path)))
+        }
+        _ => Err(format!("expected `NAME=PATH`, found `{value}`")),
+    }
+}
+
+fn parse_github_commit(value: &str) -> Result<(String, String, String), String> {
+    let parsed = value.split_once('@').and_then(|(repository, sha)| {
+        let (owner, repo) = repository.split_once('/')?;
+        [owner, repo, sha]
+            .iter()
+            .all(|part| !part.is_empty() && !part.contains('/'))
+            .then(|| (owner.to_string(), repo.to_string(), sha.to_string()))
+    });
+    parsed.ok_or_else(|| format!("expected `OWNER/REPO@SHA`, found `{value}`"))
+}
+
+#[test]
+fn compare_flag_parsing() {
+    assert_eq!(
+        parse_compare_flag("approved-by=someone"),
+        Ok(("approved-by".to_string(), "someone".to_string()))
+    );
+    assert_eq!(
+        parse_compare_flag("ticket=a=b"),
+        Ok(("ticket".to_string(), "a=b".to_string()))
+    );
+    assert!(parse_compare_flag("no-value").is_err());
+    assert!(parse_compare_flag("=value").is_err());
+}
+
+#[test]
+fn dependency_override_parsing() {
+    assert_eq!(
+        parse_dependency_override("serde=../serde"),
+        Ok(("serde".to_string(), PathBuf::from("../serde")))
+    );
+    assert!(parse_dependency_override("serde").is_err());
+    assert!(parse_dependency_override("serde=").is_err());
+    assert!(parse_dependency_override("=../serde").is_err());
+}
+
+#[test]
+fn github_commit_parsing() {
+    assert_eq!(
+        parse_github_commit("obi1kenobi/cargo-semver-checks@1a2b3c"),
+        Ok((
+            "obi1kenobi".to_string(),
+            "cargo-semver-checks".to_string(),
+            "1a2b3c".to_string()
+        ))
+    );
+    assert!(parse_github_commit("cargo-semver-checks@1a2b3c").is_err());
+    assert!(parse_github_commit("obi1kenobi/cargo-semver-checks").is_err());
+    assert!(parse_github_commit("obi1kenobi/@1a2b3c").is_err());
+    assert!(parse_github_commit("a/b/c@1a2b3c").is_err());
+}
+
+#[test]
+fn config_file_parsing() {
+    let config: ConfigFile = toml::from_str(
+        r#"
+        pedantic = true
+        metadata = { policy = "org-wide" }
+        environment = { RUSTFLAGS = "--cfg semver_checks" }
+        warn = ["function_missing"]
+        "#,
+    )
+    .unwrap();
+    assert!(config.pedantic);
+    assert_eq!(config.warn, ["function_missing"]);
+    assert!(!config.compare_docs);
+    assert_eq!(config.metadata["policy"], "org-wide");
+    assert_eq!(config.environment["RUSTFLAGS"], "--cfg semver_checks");
+
+    let json: ConfigFile = serde_json::from_str(r#"{"compare-docs": true}"#).unwrap();
+    assert!(json.compare_docs);
+    assert!(toml::from_str::<ConfigFile>("unknown-setting = true").is_err());
+}
+
+#[test]
+fn config_file_settings_are_applied() {
+    fn check_release_args(args: &[&str]) -> CheckRelease {
+        let args = ["cargo", "semver-checks", "check-release"]
+            .iter()
+            .chain(args);
+        let Cargo::SemverChecks(args) = Cargo::try_parse_from(args).unwrap();
+        let Some(SemverChecksCommands::CheckRelease(check_release)) = args.command else {
+            panic!("expected the check-release command");
+        };
+        check_release
+    }
+
+    let config: ConfigFile = toml::from_str(
+        r#"
+        no-cache = true
+        features = ["std"]
+        release-type = "minor"
+        baseline-rev = "v1.0.0"
+        baseline-docker-image = "rust:1.69"
+        target-spec-json = "target.json"
+        dependency-overrides = { serde = "../serde" }
+        lints = { function_missing = "allow", trait_missing = "warn" }
+        "#,
+    )
+    .unwrap();
+    let mut args = check_release_args(&["--deny", "trait_missing"]);
+    config.apply(&mut args).unwrap();
+    assert!(args.no_cache);
+    assert_eq!(args.features, ["std"]);
+    assert_eq!(args.release_type, Some(ReleaseType::Minor));
+    assert_eq!(args.baseline_rev.as_deref(), Some("v1.0.0"));
+    assert_eq!(args.baseline_docker_image.as_deref(), Some("rust:1.69"));
+    assert_eq!(args.target_spec_json, Some(PathBuf::from("target.json")));
+    assert_eq!(
+        args.dependency_overrides,
+        [("serde".to_string(), PathBuf::from("../serde"))]
+    );
+    assert_eq!(args.allow, ["function_missing"]);
+    assert!(args.warn.is_empty());
+    assert_eq!(args.deny, ["trait_missing"]);
+
+    // The command line takes precedence.
+    let config: ConfigFile = toml::from_str(
+        r#"
+        features = ["std"]
+        release-type = "minor"
+        baseline-rev = "v1.0.0"
+        "#,
+    )
+    .unwrap();
+    let mut args = check_release_args(&[
+        "--all-features",
+        "--assume-no-break",
+        "--baseline-version",
+        "1.0.0",
+    ]);
+    config.apply(&mut args).unwrap();
+    assert!(args.features.is_empty());
+    assert_eq!(args.release_type, None);
+    assert_eq!(args.baseline_rev, None);
+    assert_eq!(args.baseline_version.as_deref(), Some("1.0.0"));
+
+    for invalid in [
+        "release-type = \"huge\"",
+        "baseline-rev = \"v1.0.0\"\nbaseline-version = \"1.0.0\"",
+        "baseline-registry-url = \"https://example.com/index\"",
+        "all-features = true\nfeatures = [\"std\"]",
+        "baseline-docker-cargo-home = \"cargo-home\"",
+    ] {
+        let config: ConfigFile = toml::from_str(invalid).unwrap();
+        assert!(
+            config.apply(&mut check_release_args(&[])).is_err(),
+            "{invalid}"
+        );
+    }
+}
+
+#[test]
+fn feature_combinations_parsing() {
+    let combinations = parse_feature_combinations(
+        r#"
+        [[combination]]
+
+        [[combination]]
+        features = ["std", "serde"]
+        no-default-features = true
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        combinations,
+        vec![
+            FeatureCombination {
+                features: vec![],
+                no_default_features: false,
+            },
+            FeatureCombination {
+                features: vec!["std".to_string(), "serde".to_string()],
+                no_default_features: true,
+            },
+        ]
+    );
+    assert!(parse_feature_combinations("").is_err());
+    assert!(parse_feature_combinations("[[combination]]\nfeature = []").is_err());
+}
+
 #[test]
 fn verify_cli() {
     use clap::CommandFactory;
     Cargo::command().debug_assert()
 }
+
+#[test]
+fn format_flag_parsing() {
+    let Cargo::SemverChecks(args) =
+        Cargo::try_parse_from(["cargo", "semver-checks", "--list", "--format", "table"]).unwrap();
+    assert_eq!(args.format, OutputFormat::Human);
+
+    let Cargo::SemverChecks(args) = Cargo::try_parse_from([
+        "cargo",
+        "semver-checks",
+        "check-release",
+        "--format",
+        "json",
+    ])
+    .unwrap();
+    let Some(SemverChecksCommands::CheckRelease(check_release)) = args.command else {
+        panic!("expected the check-release command");
+    };
+    assert_eq!(check_release.report_format, OutputFormat::Json);
+
+    assert!(Cargo::try_parse_from(["cargo", "semver-checks", "--format", "json"]).is_err());
+}
+
+#[test]
+fn list_conflicts() {
+    for args in [
+        &["cargo", "semver-checks", "--list", "check-release"][..],
+        &["cargo", "semver-checks", "check-release", "--list"],
+        &[
+            "cargo",
+            "semver-checks",
+            "--list",
+            "--explain",
+            "function_missing",
+        ],
+    ] {
+        assert!(Cargo::try_parse_from(args).is_err(), "{args:?}");
+    }
+    assert!(
+        Cargo::try_parse_from(["cargo", "semver-checks", "--list", "--group-by", "category"])
+            .is_ok()
+    );
+}
@@ -1,7 +1,14 @@
-use std::collections::BTreeMap;
-
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    sync::{Arc, OnceLock},
+};
+
+use anyhow::Context;
+use handlebars::Template;
 use serde::{Deserialize, Serialize};
 use trustfall::TransparentValue;
+use trustfall_core::interpreter::InterpretedQuery;
 
 use crate::ReleaseType;
 
@@ -30,6 +37,60 @@ impl From<RequiredSemverUpdate> for ReleaseType {
     }
 }
 
+/// How violations of a lint affect the outcome of the check.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum LintLevel {
+    /// Violations fail the check, unless the version change already allows them.
+    #[default]
+    Deny,
+    /// Violations are reported, but never fail the check.
+    Warn,
+    /// Informational changes that are not semver-relevant, like edited documentation.
+    /// These lints only run when requested, and never fail the check.
+    Info,
+    /// The lint doesn't run at all.
+    Allow,
+}
+
+impl LintLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Deny => "deny",
+            Self::Warn => "warn",
+            Self::Info => "info",
+            Self::Allow => "allow",
+        }
+    }
+}
+
+/// The kind of item a lint checks, for grouping lints in listings and documentation.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LintGroup {
+    /// Functions, including methods.
+    Functions,
+    Traits,
+    /// Structs, enums and their variants and fields.
+    Types,
+    /// The trait implementations of types.
+    Impls,
+    /// Lints that don't check one kind of item, like broken documentation links.
+    Other,
+}
+
+impl LintGroup {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Functions => "functions",
+            Self::Traits => "traits",
+            Self::Types => "types",
+            Self::Impls => "impls",
+            Self::Other => "other",
+        }
+    }
+}
+
 /// Kind of semver update.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ActualSemverUpdate {
@@ -65,17 +126,30 @@ impl From<ReleaseType> for ActualSemverUpdate {
 #[non_exhaustive]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SemverQuery {
+    /// Unique identifier of the lint, e.g. `function_missing`.
     pub id: String,
 
-    pub(crate) human_readable_name: String,
+    /// Short human-readable summary of the lint, e.g. `pub fn removed or renamed`.
+    pub human_readable_name: String,
 
+    /// One-sentence description of what the lint detects.
     pub description: String,
 
+    /// Minimum version bump that makes violations of this lint acceptable.
     pub required_update: RequiredSemverUpdate,
 
+    /// The kind of item the lint checks.
+    pub group: LintGroup,
+
+    /// Whether violations of this lint fail the check or are only reported as warnings.
+    #[serde(default)]
+    pub lint_level: LintLevel,
+
+    /// Optional longer explanation of the lint, shown by `--explain` instead of the description.
     #[serde(default)]
     pub reference: Option<String>,
 
+    /// Optional link to further reading, e.g. the relevant section of the cargo semver reference.
     #[serde(default)]
     pub reference_link: Option<String>,
 
@@ -87,7 +161,12 @@ pub struct SemverQuery {
     /// The top-level error describing the semver violation that was detected.
     /// Even if multiple instances of this semver issue are found, this error
     /// message is displayed only at most once.
-    pub(crate) error_message: String,
+    pub error_message: String,
+
+    /// Optional advice on how to resolve violations of this lint,
+    /// e.g. which change to revert or which version bump to make.
+    #[serde(default)]
+    pub suggested_fix: Option<String>,
 
     /// Optional template that can be combined with each query output to produce
     /// a human-readable description of the specific semver violation that was discovered.
@@ -114,6 +193,134 @@ Failed to parse a query: {e}
 
         queries
     }
+
+    /// The semver update required when removing a public item of the given kind,
+    /// e.g. `"function"`, `"struct"`, `"const"` or `"type"`, without running any query.
+    ///
+    /// Where a lint reports the removal of that kind of item, its required update is used.
+    /// Removing any other kind of public item, such as a constant or a module,
+    /// is also a major change. Returns `None` for unrecognized item kinds.
+    pub fn severity_for(item_kind: &str) -> Option<RequiredSemverUpdate> {
+        let lint_id = match item_kind {
+            "struct" => "struct_missing",
+            "enum" => "enum_missing",
+            "variant" => "enum_variant_missing",
+            "function" => "function_missing",
+            "method" => "inherent_method_missing",
+            "struct_field" => "struct_pub_field_missing",
+            "trait" => "trait_missing",
+            "union" | "module" | "const" | "constant" | "static" | "type" | "typedef"
+            | "type_alias" | "macro" | "trait_alias" | "assoc_const" | "assoc_type" => {
+                return Some(RequiredSemverUpdate::Major)
+            }
+            _ => return None,
+        };
+        // Loading all lints parses every one of them, so only do it once.
+        static REQUIRED_UPDATES: OnceLock<BTreeMap<String, RequiredSemverUpdate>> = OnceLock::new();
+        let required_updates = REQUIRED_UPDATES.get_or_init(|| {
+            Self::all_queries()
+                .into_iter()
+                .map(|(id, query)| (id, query.required_update))
+                .collect()
+        });
+        Some(required_updates[lint_id])
+    }
+
+    /// The kind of item the lint checks, for grouping lints in listings and documentation:
+    /// `"functions"` (including methods), `"traits"`, `"types"`, or `"impls"`
+    /// for the trait implementations of types. See [`SemverQuery::group`].
+    pub fn lint_group(&self) -> &'static str {
+        self.group.as_str()
+    }
+
+    /// The full explanation of the lint, as printed by `--explain`:
+    /// its reference text (or description), its suggested fix, and its reference link.
+    pub fn explanation(&self) -> String {
+        let mut explanation = self
+            .reference
+            .as_deref()
+            .unwrap_or(self.description.as_str())
+            .to_string();
+        if let Some(suggested_fix) = &self.suggested_fix {
+            explanation.push_str(&format!("\n\nSuggested fix: {suggested_fix}"));
+        }
+        if let Some(link) = &self.reference_link {
+            explanation.push_str(&format!("\n\nSee also {link}"));
+        }
+        explanation
+    }
+
+    /// Check that the file at `path` contains a well-formed query definition,
+    /// without running the query against any rustdoc data.
+    ///
+    /// The file must be valid RON describing a [`SemverQuery`] whose `id` matches the file name,
+    /// whose human-readable fields are non-empty, whose `group` names the kind of item it checks, whose Trustfall query is valid
+    /// against the rustdoc schema and is given exactly the arguments it uses,
+    /// and whose error template (if any) is a valid template.
+    ///
+    /// Only the schema of the newest supported rustdoc format, v24, is checked.
+    /// The schemas of older formats are only available from an adapter loaded with
+    /// rustdoc data, so a query using something new in v24 still passes validation
+    /// but fails when checking crates documented by an older toolchain.
+    pub fn validate_ron_file(path: &Path) -> anyhow::Result<()> {
+        let query_text = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read query file {}", path.display()))?;
+        let query: SemverQuery = ron::from_str(&query_text)
+            .with_context(|| format!("failed to parse query file {}", path.display()))?;
+
+        query
+            .validate(path)
+            .with_context(|| format!("invalid query in file {}", path.display()))
+    }
+
+    fn validate(&self, path: &Path) -> anyhow::Result<()> {
+        let required_fields = [
+            ("id", &self.id),
+            ("human_readable_name", &self.human_readable_name),
+            ("description", &self.description),
+            ("query", &self.query),
+            ("error_message", &self.error_message),
+        ];
+        for (field_name, value) in required_fields {
+            if value.trim().is_empty() {
+                anyhow::bail!("field `{field_name}` must not be empty");
+            }
+        }
+
+        // Only lints built into the tool itself check something other than an item.
+        if self.group == LintGroup::Other {
+            anyhow::bail!("field `group` must name the kind of item the lint checks");
+        }
+
+        // The query's implementation link in the check-release output assumes
+        // that each query lives in a file named after its id.
+        let file_stem = path.file_stem().and_then(|stem| stem.to_str());
+        if file_stem != Some(self.id.as_str()) {
+            anyhow::bail!(
+                "query id `{}` does not match the file name {}",
+                self.id,
+                path.display()
+            );
+        }
+
+        // The adapter of the newest rustdoc format, see `validate_ron_file()`.
+        let schema = trustfall_rustdoc_adapter::RustdocAdapter::schema();
+        let parsed_query = trustfall_core::frontend::parse(&schema, &self.query)
+            .context("query is not valid against the rustdoc schema")?;
+        let arguments = self
+            .arguments
+            .iter()
+            .map(|(name, value)| (Arc::from(name.as_str()), value.clone().into()))
+            .collect();
+        InterpretedQuery::from_query_and_arguments(parsed_query, Arc::new(arguments))
+            .context("query arguments do not match the variables used in the query")?;
+
+        if let Some(template) = &self.per_result_error_template {
+            Template::compile(template).context("`per_result_error_template` is not valid")?;
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -126,7 +333,7 @@ mod tests {
         load_rustdoc, VersionedCrate, VersionedIndexedCrate, VersionedRustdocAdapter,
     };
 
-    use crate::query::SemverQuery;
+    use crate::query::{LintGroup, RequiredSemverUpdate, SemverQuery};
     use crate::templating::make_handlebars_registry;
 
     lazy_static::lazy_static! {
@@ -211,6 +418,97 @@ mod tests {
         }
     }
 
+    #[test]
+    fn all_lint_files_pass_validation() {
+        for dir_entry in std::fs::read_dir("./src/lints/").expect("directory src/lints/ not found")
+        {
+            let path = dir_entry.expect("failed to list src/lints/").path();
+            SemverQuery::validate_ron_file(&path)
+                .unwrap_or_else(|e| panic!("{} failed validation: {e:#}", path.display()));
+        }
+    }
+
+    #[test]
+    fn severity_for_item_kinds() {
+        assert_eq!(
+            SemverQuery::severity_for("function"),
+            Some(RequiredSemverUpdate::Major)
+        );
+        assert_eq!(
+            SemverQuery::severity_for("constant"),
+            Some(RequiredSemverUpdate::Major)
+        );
+        assert_eq!(
+            SemverQuery::severity_for("const"),
+            Some(RequiredSemverUpdate::Major)
+        );
+        assert_eq!(
+            SemverQuery::severity_for("type"),
+            Some(RequiredSemverUpdate::Major)
+        );
+        assert_eq!(SemverQuery::severity_for("not_an_item_kind"), None);
+    }
+
+    #[test]
+    fn every_lint_has_a_group() {
+        let queries = SemverQuery::all_queries();
+        for query in queries.values() {
+            assert_ne!(query.group, LintGroup::Other, "{}", query.id);
+        }
+        assert_eq!(queries["function_missing"].lint_group(), "functions");
+        assert_eq!(queries["trait_missing"].lint_group(), "traits");
+        assert_eq!(queries["auto_trait_impl_removed"].lint_group(), "impls");
+        assert_eq!(queries["enum_variant_added"].lint_group(), "types");
+    }
+
+    #[test]
+    fn validation_rejects_unused_arguments() {
+        let query_text = std::fs::read_to_string("./src/lints/function_missing.ron").unwrap();
+        let query_text = query_text.replacen(
+            r#""public": "public","#,
+            r#""public": "public", "unused": 1,"#,
+            1,
+        );
+
+        // The file must be named after the query, so it gets a directory of its own
+        // that concurrent test runs don't share.
+        let dir = std::env::temp_dir().join(format!(
+            "cargo-semver-checks-validate-query-test-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("function_missing.ron");
+        std::fs::write(&path, query_text).unwrap();
+
+        let result = SemverQuery::validate_ron_file(&path);
+        std::fs::remove_dir_all(&dir).unwrap();
+        let error = result.expect_err("validation passed");
+        assert!(format!("{error:#}").contains("unused"), "{error:#}");
+    }
+
+    #[test]
+    fn validation_requires_a_group() {
+        let query_text = std::fs::read_to_string("./src/lints/function_missing.ron").unwrap();
+        let dir = std::env::temp_dir().join(format!(
+            "cargo-semver-checks-validate-group-test-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("function_missing.ron");
+
+        let mut errors = vec![];
+        for group in ["", "    group: Other,\n"] {
+            let query_text = query_text.replacen("    group: Functions,\n", group, 1);
+            std::fs::write(&path, query_text).unwrap();
+            errors.push(SemverQuery::validate_ron_file(&path));
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+        for result in errors {
+            let error = result.expect_err("validation passed");
+            assert!(format!("{error:#}").contains("group"), "{error:#}");
+        }
+    }
+
     #[test]
     fn pub_use_handling() {
         let (_baseline_crate, current_crate) = &TEST_CRATE_RUSTDOCS["pub_use_handling"];
@@ -503,4 +801,29 @@ add_lints!(
     variant_marked_non_exhaustive,
     enum_tuple_variant_field_missing,
     enum_tuple_variant_field_added,
+    debug_impl_removed,
+    type_moved_behind_feature_flag,
+    function_moved_behind_feature_flag,
+    trait_moved_behind_feature_flag,
+    inline_attribute_changed,
+    inline_attribute_added,
+    trait_method_param_renamed,
+    method_moved_to_trait,
+    type_docs_changed,
+    function_docs_changed,
+    trait_docs_changed,
+    drop_impl_added,
+    trait_method_async_changed,
+    repr_c_field_order_changed,
+    function_must_use_removed,
+    type_must_use_removed,
+    function_now_doc_hidden,
+    type_now_doc_hidden,
+    trait_method_unsafe_added,
+    function_unsafe_removed,
+    function_stabilized,
+    type_stabilized,
+    trait_stabilized,
+    trait_method_parameter_count_changed,
+    negative_impl_added,
 );
@@ -0,0 +1,556 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use clap::crate_version;
+use itertools::Itertools;
+use termcolor::Color;
+use termcolor_output::{colored, colored_ln};
+use trustfall::TransparentValue;
+
+use crate::{
+    query::{LintLevel, RequiredSemverUpdate},
+    CrateReport, GlobalConfig, Report, SemverQuery,
+};
+
+/// A single instance of a semver violation found by one of the lints.
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub struct Violation {
+    crate_name: String,
+    query: Arc<SemverQuery>,
+    values: BTreeMap<Arc<str>, TransparentValue>,
+    message: Option<String>,
+    fails_check: bool,
+}
+
+impl Violation {
+    pub(crate) fn new(
+        crate_name: String,
+        query: Arc<SemverQuery>,
+        values: BTreeMap<Arc<str>, TransparentValue>,
+        message: Option<String>,
+        fails_check: bool,
+    ) -> Self {
+        Self {
+            crate_name,
+            query,
+            values,
+            message,
+            fails_check,
+        }
+    }
+
+    /// Name of the crate in which the violation was found.
+    pub fn crate_name(&self) -> &str {
+        &self.crate_name
+    }
+
+    /// The lint that found the violation.
+    pub fn query(&self) -> &SemverQuery {
+        &self.query
+    }
+
+    /// Identifier of the lint that found the violation, e.g. `function_missing`.
+    pub fn query_id(&self) -> &str {
+        &self.query.id
+    }
+
+    /// Minimum version bump that would make this violation acceptable.
+    pub fn required_update(&self) -> RequiredSemverUpdate {
+        self.query.required_update
+    }
+
+    /// Level of the lint that found the violation, including any override
+    /// such as [`Check::with_lint_level()`](crate::Check::with_lint_level).
+    pub fn lint_level(&self) -> LintLevel {
+        self.query.lint_level
+    }
+
+    /// Whether this violation fails the check. That's the case for [`LintLevel::Deny`] lints,
+    /// and for all lints in [pedantic mode](crate::Check::with_pedantic).
+    pub fn fails_check(&self) -> bool {
+        self.fails_check
+    }
+
+    /// Human-readable description of this specific violation,
+    /// if the lint defines a template for one.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// Advice on how to resolve this violation, if the lint provides any.
+    pub fn suggested_fix(&self) -> Option<&str> {
+        self.query.suggested_fix.as_deref()
+    }
+
+    /// Path of the affected item, e.g. `my_crate::my_mod::MyStruct`,
+    /// if the lint reports one.
+    ///
+    /// For violations concerning part of an item, like a method or a field,
+    /// this is the path of the item that contains it.
+    pub fn item_path(&self) -> Option<String> {
+        match self.values.get("path")? {
+            TransparentValue::List(segments) => segments
+                .iter()
+                .map(|segment| match segment {
+                    TransparentValue::String(segment) => Some(segment.as_ref()),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()
+                .map(|segments| segments.join("::")),
+            _ => None,
+        }
+    }
+
+    /// The raw values output by the lint's query for this violation.
+    pub fn values(&self) -> &BTreeMap<Arc<str>, TransparentValue> {
+        &self.values
+    }
+}
+
+/// Receives the results of a semver check as they become available.
+///
+/// All methods have a default no-op implementation,
+/// so implementors only need to override the events they care about.
+pub trait Reporter {
+    /// Called before the lints are run on a package.
+    /// The version is `None` if it could not be determined, e.g. for rustdoc JSON inputs.
+    fn on_package_start(
+        &mut self,
+        _name: &str,
+        _version: Option<&semver::Version>,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Called for each semver violation found in the package being checked.
+    /// Violations of the same lint are reported consecutively.
+    fn on_violation(&mut self, _violation: &Violation) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Called after all the violations in a package have been reported.
+    fn on_package_complete(&mut self, _name: &str, _report: &CrateReport) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Called once all packages have been checked.
+    fn on_complete(&mut self, _report: &Report) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+impl<R: Reporter + ?Sized> Reporter for &mut R {
+    fn on_package_start(
+        &mut self,
+        name: &str,
+        version: Option<&semver::Version>,
+    ) -> anyhow::Result<()> {
+        (**self).on_package_start(name, version)
+    }
+
+    fn on_violation(&mut self, violation: &Violation) -> anyhow::Result<()> {
+        (**self).on_violation(violation)
+    }
+
+    fn on_package_complete(&mut self, name: &str, report: &CrateReport) -> anyhow::Result<()> {
+        (**self).on_package_complete(name, report)
+    }
+
+    fn on_complete(&mut self, report: &Report) -> anyhow::Result<()> {
+        (**self).on_complete(report)
+    }
+}
+
+/// Holds back the events of one package's check, so that packages can be checked
+/// in parallel while their results still reach the actual reporter one package at a time.
+#[derive(Debug, Default)]
+pub(crate) struct BufferedReporter {
+    events: Vec<BufferedEvent>,
+}
+
+#[derive(Debug)]
+enum BufferedEvent {
+    PackageStart(String, Option<semver::Version>),
+    Violation(Violation),
+    PackageComplete(String),
+}
+
+impl BufferedReporter {
+    /// Pass the held-back events on to `reporter`, in the order they happened.
+    /// The package is completed with `report`, the one its check returned.
+    pub(crate) fn replay(
+        self,
+        reporter: &mut dyn Reporter,
+        report: &CrateReport,
+    ) -> anyhow::Result<()> {
+        for event in self.events {
+            match event {
+                BufferedEvent::PackageStart(name, version) => {
+                    reporter.on_package_start(&name, version.as_ref())?
+                }
+                BufferedEvent::Violation(violation) => reporter.on_violation(&violation)?,
+                BufferedEvent::PackageComplete(name) => {
+                    reporter.on_package_complete(&name, report)?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Reporter for BufferedReporter {
+    fn on_package_start(
+        &mut self,
+        name: &str,
+        version: Option<&semver::Version>,
+    ) -> anyhow::Result<()> {
+        self.events.push(BufferedEvent::PackageStart(
+            name.to_string(),
+            version.cloned(),
+        ));
+        Ok(())
+    }
+
+    fn on_violation(&mut self, violation: &Violation) -> anyhow::Result<()> {
+        self.events
+            .push(BufferedEvent::Violation(violation.clone()));
+        Ok(())
+    }
+
+    fn on_package_complete(&mut self, name: &str, _report: &CrateReport) -> anyhow::Result<()> {
+        self.events
+            .push(BufferedEvent::PackageComplete(name.to_string()));
+        Ok(())
+    }
+}
+
+/// The default reporter, which prints violations to the terminal.
+pub struct TerminalReporter {
+    config: GlobalConfig,
+    /// The lint whose violations are currently being printed, if any.
+    current_query_id: Option<String>,
+    /// Whether to print each lint's full explanation along with its violations.
+    explain: bool,
+}
+
+impl TerminalReporter {
+    pub fn new(config: GlobalConfig) -> Self {
+        Self {
+            config,
+            current_query_id: None,
+            explain: false,
+        }
+    }
+
+    /// Also print the full explanation of each lint that found violations,
+    /// the same text shown by `--explain`.
+    pub fn with_explanations(mut self, yes: bool) -> Self {
+        self.explain = yes;
+        self
+    }
+
+    fn print_query_header(&mut self, semver_query: &SemverQuery) -> anyhow::Result<()> {
+        let kind = match semver_query.lint_level {
+            LintLevel::Deny => "failure",
+            LintLevel::Warn => "warning",
+            LintLevel::Info | LintLevel::Allow => "info",
+        };
+        colored_ln(self.config.stdout(), |w| {
+            colored!(
+                w,
+                "\n--- {} {}: {} ---\n",
+                kind,
+                &semver_query.id,
+                &semver_query.human_readable_name,
+            )
+        })?;
+
+        let impl_link = format!(
+            "https://github.com/obi1kenobi/cargo-semver-check/tree/v{}/src/lints/{}.ron",
+            crate_version!(),
+            semver_query.id,
+        );
+        if let Some(ref_link) = semver_query.reference_link.as_deref() {
+            colored_ln(self.config.stdout(), |w| {
+                colored!(
+                    w,
+                    "{}Description:{}\n{}\n{:>12} {}\n{:>12} {}\n",
+                    bold!(true),
+                    reset!(),
+                    &semver_query.error_message,
+                    "ref:",
+                    ref_link,
+                    "impl:",
+                    impl_link,
+                )
+            })?;
+        } else {
+            colored_ln(self.config.stdout(), |w| {
+                colored!(
+                    w,
+                    "{}Description:{}\n{}\n{:>12} {}\n",
+                    bold!(true),
+                    reset!(),
+                    &semver_query.error_message,
+                    "impl:",
+                    impl_link,
+                )
+            })?;
+        }
+
+        if self.explain {
+            colored_ln(self.config.stdout(), |w| {
+                colored!(
+                    w,
+                    "{}Explanation:{}\n{}\n",
+                    bold!(true),
+                    reset!(),
+                    semver_query.explanation(),
+                )
+            })?;
+        } else if let Some(suggested_fix) = semver_query.suggested_fix.as_deref() {
+            colored_ln(self.config.stdout(), |w| {
+                colored!(
+                    w,
+                    "{}Suggested fix:{}\n{}\n",
+                    bold!(true),
+                    reset!(),
+                    suggested_fix,
+                )
+            })?;
+        }
+
+        let location_heading = match semver_query.lint_level {
+            LintLevel::Deny => "Failed in:",
+            LintLevel::Warn => "Found in:",
+            LintLevel::Info | LintLevel::Allow => "Changed in:",
+        };
+        colored_ln(self.config.stdout(), |w| {
+            colored!(w, "{}{}{}", bold!(true), location_heading, reset!(),)
+        })?;
+        Ok(())
+    }
+}
+
+impl Reporter for TerminalReporter {
+    fn on_violation(&mut self, violation: &Violation) -> anyhow::Result<()> {
+        if self.current_query_id.as_deref() != Some(violation.query_id()) {
+            self.print_query_header(violation.query())?;
+            self.current_query_id = Some(violation.query_id().to_string());
+        }
+
+        if let Some(message) = violation.message() {
+            colored_ln(self.config.stdout(), |w| colored!(w, "  {}", message,))?;
+
+            self.config.extra_verbose(|config| {
+                colored_ln(config.stdout(), |w| {
+                    let serde_pretty =
+                        serde_json::to_string_pretty(violation.values()).expect("serde failed");
+                    let indented_serde = serde_pretty
+                        .split('\n')
+                        .map(|line| format!("    {line}"))
+                        .join("\n");
+                    colored!(w, "    lint rule output values:\n{}", indented_serde)
+                })
+                .map_err(|e| e.into())
+            })?;
+        } else {
+            colored_ln(self.config.stdout(), |w| {
+                colored!(
+                    w,
+                    "{}\n",
+                    serde_json::to_string_pretty(violation.values()).expect("serde failed"),
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    fn on_package_complete(&mut self, _name: &str, report: &CrateReport) -> anyhow::Result<()> {
+        self.current_query_id = None;
+
+        if report.required_bump().is_some() {
+            let failed_queries = report.failed_queries();
+            let required_bump = if failed_queries.contains(&RequiredSemverUpdate::Major) {
+                RequiredSemverUpdate::Major
+            } else {
+                RequiredSemverUpdate::Minor
+            };
+            self.config.shell_print(
+                "Final",
+                format_args!(
+                    "[{:>8.3}s] semver requires new {} version: {} major and {} minor checks failed",
+                    report.duration().as_secs_f32(),
+                    required_bump.as_str(),
+                    failed_queries
+                        .iter()
+                        .filter(|x| **x == RequiredSemverUpdate::Major)
+                        .count(),
+                    failed_queries
+                        .iter()
+                        .filter(|x| **x == RequiredSemverUpdate::Minor)
+                        .count(),
+                ),
+                Color::Red,
+                true,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A reporter that writes a JUnit XML document once all packages have been checked.
+///
+/// Each package is a `<testsuite>`, and each violation is a `<testcase>` with a `<failure>`.
+/// Packages without violations get a single passing `<testcase>`, so that they still
+/// show up in CI dashboards.
+pub struct JunitReporter<W> {
+    writer: W,
+}
+
+impl<W: std::io::Write> JunitReporter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+}
+
+impl<W: std::io::Write> Reporter for JunitReporter<W> {
+    fn on_complete(&mut self, report: &Report) -> anyhow::Result<()> {
+        let total_tests: usize = report
+            .crate_reports()
+            .values()
+            .map(|crate_report| crate_report.violations().len().max(1))
+            .sum();
+        let total_failures: usize = report
+            .crate_reports()
+            .values()
+            .map(|crate_report| count_failures(crate_report.violations()))
+            .sum();
+
+        let w = &mut self.writer;
+        writeln!(w, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            w,
+            r#"<testsuites name="cargo-semver-checks" tests="{total_tests}" failures="{total_failures}">"#
+        )?;
+        for (crate_name, crate_report) in report.crate_reports() {
+            let violations = crate_report.violations();
+            let crate_name = xml_escape(crate_name);
+            writeln!(
+                w,
+                r#"  <testsuite name="{crate_name}" tests="{}" failures="{}">"#,
+                violations.len().max(1),
+                count_failures(violations),
+            )?;
+            if violations.is_empty() {
+                writeln!(
+                    w,
+                    r#"    <testcase name="semver-checks" classname="{crate_name}"/>"#
+                )?;
+            }
+            for violation in violations {
+                let query = violation.query();
+                let description = match violation.message() {
+                    Some(message) => message.to_string(),
+                    None => serde_json::to_string(violation.values())?,
+                };
+                writeln!(
+                    w,
+                    r#"    <testcase name="{}" classname="{crate_name}.{}">"#,
+                    xml_escape(&description),
+                    xml_escape(&query.id),
+                )?;
+                match (violation.fails_check(), violation.lint_level()) {
+                    (true, _) => writeln!(
+                        w,
+                        r#"      <failure type="{}" message="{}">{}</failure>"#,
+                        violation.required_update().as_str(),
+                        xml_escape(&query.human_readable_name),
+                        xml_escape(&query.error_message),
+                    )?,
+                    // JUnit has no notion of warnings, so they are passing testcases
+                    // whose output explains the issue.
+                    (false, LintLevel::Warn) => writeln!(
+                        w,
+                        "      <system-out>warning: {}</system-out>",
+                        xml_escape(&query.error_message),
+                    )?,
+                    (false, _) => writeln!(
+                        w,
+                        "      <system-out>info: {}</system-out>",
+                        xml_escape(&query.error_message),
+                    )?,
+                }
+                writeln!(w, "    </testcase>")?;
+            }
+            writeln!(w, "  </testsuite>")?;
+        }
+        writeln!(w, "</testsuites>")?;
+        w.flush()?;
+        Ok(())
+    }
+}
+
+/// A reporter that writes the JSON form of the report, as returned by
+/// [`Report::to_json()`], once all packages have been checked.
+pub struct JsonReporter<W> {
+    writer: W,
+}
+
+impl<W: std::io::Write> JsonReporter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+}
+
+impl<W: std::io::Write> Reporter for JsonReporter<W> {
+    fn on_complete(&mut self, report: &Report) -> anyhow::Result<()> {
+        serde_json::to_writer_pretty(&mut self.writer, &report.to_json())?;
+        writeln!(self.writer)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// A reporter that writes the SARIF 2.1.0 form of the report, as returned by
+/// [`Report::to_sarif()`], once all packages have been checked.
+pub struct SarifReporter<W> {
+    writer: W,
+}
+
+impl<W: std::io::Write> SarifReporter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+}
+
+impl<W: std::io::Write> Reporter for SarifReporter<W> {
+    fn on_complete(&mut self, report: &Report) -> anyhow::Result<()> {
+        serde_json::to_writer_pretty(&mut self.writer, &report.to_sarif())?;
+        writeln!(self.writer)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+fn count_failures(violations: &[Violation]) -> usize {
+    violations
+        .iter()
+        .filter(|violation| violation.fails_check())
+        .count()
+}
+
+fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
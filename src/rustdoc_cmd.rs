@@ -1,23 +1,39 @@
-use std::path::{Path, PathBuf};
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    time::{Duration, Instant},
+};
 
 use anyhow::Context;
 
 use crate::{
-    rustdoc_gen::{CrateDataForRustdoc, CrateSource},
+    rustdoc_gen::{
+        is_network_failure_message, retry_network_operation, CrateDataForRustdoc, CrateSource,
+    },
     GlobalConfig,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct RustdocCommand {
     deps: bool,
-    silence: bool,
+    env: HashMap<String, String>,
+    deadline: Option<Instant>,
+    use_cache: bool,
+    target_spec: Option<PathBuf>,
+    patches: Vec<(String, PathBuf)>,
 }
 
 impl RustdocCommand {
     pub(crate) fn new() -> Self {
         Self {
             deps: false,
-            silence: false,
+            env: HashMap::new(),
+            deadline: None,
+            use_cache: true,
+            target_spec: None,
+            patches: Vec::new(),
         }
     }
 
@@ -37,12 +53,140 @@ impl RustdocCommand {
         self
     }
 
-    /// Don't write progress to stderr
-    pub(crate) fn silence(mut self, yes: bool) -> Self {
-        self.silence = yes;
+    /// Environment variables to set on the spawned `cargo` processes.
+    ///
+    /// A `RUSTDOCFLAGS` value is combined with the flags needed to emit rustdoc JSON
+    /// rather than replacing them.
+    pub(crate) fn env(mut self, env: HashMap<String, String>) -> Self {
+        self.env = env;
+        self
+    }
+
+    /// Stop `cargo doc` and return an error if it is still running at the given instant.
+    pub(crate) fn deadline(mut self, deadline: Option<Instant>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Whether previously generated rustdoc of registry crates may be reused.
+    ///
+    /// When disabled, the rustdoc is regenerated and replaces the cached copy.
+    pub(crate) fn use_cache(mut self, yes: bool) -> Self {
+        self.use_cache = yes;
+        self
+    }
+
+    pub(crate) fn is_cache_enabled(&self) -> bool {
+        self.use_cache
+    }
+
+    /// Document the crate for the custom target described by the given target spec JSON file.
+    pub(crate) fn target_spec(mut self, target_spec: Option<PathBuf>) -> Self {
+        self.target_spec = target_spec;
+        self
+    }
+
+    /// Replace the named crates.io dependencies with the crates at the given local paths,
+    /// as if by a `[patch.crates-io]` section in the manifest.
+    pub(crate) fn patches(mut self, patches: Vec<(String, PathBuf)>) -> Self {
+        self.patches = patches;
         self
     }
 
+    pub(crate) fn has_patches(&self) -> bool {
+        !self.patches.is_empty()
+    }
+
+    /// A path-safe suffix distinguishing cached rustdoc generated with the environment
+    /// set through [`RustdocCommand::env()`], since e.g. `RUSTFLAGS` can change the API.
+    /// The values are hashed rather than included, as they may hold secrets.
+    pub(crate) fn env_cache_suffix(&self) -> String {
+        use std::hash::{Hash, Hasher};
+
+        if self.env.is_empty() {
+            return String::new();
+        }
+        let mut env: Vec<_> = self.env.iter().collect();
+        env.sort();
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        env.hash(&mut hasher);
+        format!("-env-{:016x}", hasher.finish())
+    }
+
+    /// The target name rustdoc is generated for, if it isn't the host.
+    ///
+    /// That's the target spec file if one was given, and otherwise the `CARGO_BUILD_TARGET`
+    /// set on the spawned processes or inherited from this one, as with `-Z build-std`.
+    /// For a target spec file, the name is the file name without its `.json` extension.
+    pub(crate) fn target_name(&self) -> Option<String> {
+        if let Some(target_spec) = &self.target_spec {
+            return target_spec
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned());
+        }
+
+        let build_target = match self.env.get("CARGO_BUILD_TARGET") {
+            Some(build_target) => build_target.clone(),
+            None => std::env::var("CARGO_BUILD_TARGET").ok()?,
+        };
+        if build_target.is_empty() {
+            return None;
+        }
+        // Like `--target`, the variable may name a target spec file instead of a triple.
+        if build_target.ends_with(".json") {
+            Path::new(&build_target)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+        } else {
+            Some(build_target)
+        }
+    }
+
+    /// The host directories a crate at `manifest_path` needs in order to build in a container:
+    /// the root of its workspace, for `workspace = true` inheritance, and the directories
+    /// of any local path dependencies outside of it, as resolved in `metadata`.
+    fn local_source_dirs(
+        &self,
+        manifest_path: &Path,
+        build_dir: &Path,
+        metadata: &cargo_metadata::Metadata,
+    ) -> anyhow::Result<Vec<PathBuf>> {
+        let mut metadata_cmd = cargo_metadata::MetadataCommand::new();
+        for (key, value) in &self.env {
+            metadata_cmd.env(key, value);
+        }
+        let workspace_root = metadata_cmd
+            .manifest_path(manifest_path)
+            .no_deps()
+            .exec()
+            .context("failed to find the workspace of the crate")?
+            .workspace_root;
+
+        let mut dirs = vec![workspace_root.into_std_path_buf()];
+        // Path dependencies are the packages without a source.
+        dirs.extend(
+            metadata
+                .packages
+                .iter()
+                .filter(|package| package.source.is_none())
+                .filter_map(|package| package.manifest_path.parent())
+                .map(|dir| dir.as_std_path().to_path_buf()),
+        );
+        let mut dirs = dirs
+            .into_iter()
+            .map(|dir| {
+                dir.canonicalize()
+                    .with_context(|| format!("failed to canonicalize {}", dir.display()))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        // The placeholder has no source either, but the build dir is mounted separately.
+        dirs.retain(|dir| !dir.starts_with(build_dir));
+        // Mounting a directory also mounts everything inside it.
+        dirs.sort();
+        dirs.dedup_by(|dir, outer| dir.starts_with(outer));
+        Ok(dirs)
+    }
+
     /// Produce a rustdoc JSON file for the specified crate and source.
     pub(crate) fn generate_rustdoc(
         &self,
@@ -54,15 +198,43 @@ impl RustdocCommand {
         // Generate an empty placeholder project with a dependency on the crate
         // whose rustdoc we need. We take this indirect generation path to avoid issues like:
         // https://github.com/obi1kenobi/cargo-semver-checks/issues/167#issuecomment-1382367128
-        let placeholder_manifest = create_placeholder_rustdoc_manifest(crate_source, crate_data)
-            .context("failed to create placeholder manifest")?;
+        let placeholder_manifest =
+            create_placeholder_rustdoc_manifest(crate_source, crate_data, &self.patches)
+                .context("failed to create placeholder manifest")?;
         let placeholder_manifest_path =
             save_placeholder_rustdoc_manifest(build_dir.as_path(), placeholder_manifest)
                 .context("failed to save placeholder rustdoc manifest")?;
 
-        let metadata = cargo_metadata::MetadataCommand::new()
-            .manifest_path(&placeholder_manifest_path)
-            .exec()?;
+        // Resolving the placeholder's metadata downloads the crate and its dependencies,
+        // so that `cargo doc` doesn't need the network afterward. Retry it if the download
+        // fails due to a transient network error.
+        let metadata = retry_network_operation(
+            config,
+            &format!("download {}", crate_source.name()?),
+            || {
+                let mut metadata_cmd = cargo_metadata::MetadataCommand::new();
+                for (key, value) in &self.env {
+                    metadata_cmd.env(key, value);
+                }
+                metadata_cmd
+                    .manifest_path(&placeholder_manifest_path)
+                    .exec()
+                    .map_err(|err| {
+                        let hint = match &err {
+                            cargo_metadata::Error::CargoMetadata { stderr } => {
+                                registry_auth_failure_hint(stderr)
+                            }
+                            _ => None,
+                        };
+                        let err = anyhow::Error::from(err);
+                        match hint {
+                            Some(hint) => err.context(hint),
+                            None => err,
+                        }
+                    })
+            },
+            |err| is_network_failure_message(&format!("{err:#}")),
+        )?;
         let placeholder_target_directory = metadata
             .target_directory
             .as_path()
@@ -71,8 +243,17 @@ impl RustdocCommand {
             .join(crate::util::SCOPE)
             .join("target");
         let target_dir = placeholder_target_directory.as_path();
+        // When building for an explicit target, whether through `--target` or
+        // `CARGO_BUILD_TARGET`, cargo puts the docs in a target-specific directory.
+        let doc_dir = match self.target_name() {
+            Some(target_name) => target_dir.join(target_name).join("doc"),
+            None => target_dir.join("doc"),
+        };
 
-        let stderr = if self.silence {
+        let silence = config.is_cargo_stderr_intercepted();
+        // Output that is forwarded to a buffered config has to be captured first.
+        let forward_captured = !silence && config.is_stderr_buffered();
+        let stderr = if silence || forward_captured {
             std::process::Stdio::piped()
         } else {
             // Print cargo doc progress
@@ -91,13 +272,61 @@ impl RustdocCommand {
         //       To generate rustdoc for our intended crate with features enabled,
         //       those features must be enabled on the dependency in the `Cargo.toml`
         //       of the placeholder project.
-        let mut cmd = std::process::Command::new("cargo");
-        cmd.env("RUSTC_BOOTSTRAP", "1")
-            .env(
-                "RUSTDOCFLAGS",
-                "-Z unstable-options --document-private-items --document-hidden-items --output-format=json --cap-lints allow",
-            )
-            .stdout(std::process::Stdio::null()) // Don't pollute output
+        let mut rustdocflags = String::from(
+            "-Z unstable-options --document-private-items --document-hidden-items --output-format=json --cap-lints allow",
+        );
+        if let Some(extra_flags) = self.env.get("RUSTDOCFLAGS") {
+            rustdocflags = format!("{extra_flags} {rustdocflags}");
+        }
+
+        let mut env: Vec<(&str, &str)> = self
+            .env
+            .iter()
+            .filter(|(key, _)| key.as_str() != "RUSTDOCFLAGS")
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect();
+        env.push(("RUSTC_BOOTSTRAP", "1"));
+        env.push(("RUSTDOCFLAGS", &rustdocflags));
+
+        let mut cmd = match crate_data.docker {
+            None => {
+                let mut cmd = std::process::Command::new("cargo");
+                cmd.envs(env);
+                cmd
+            }
+            Some(docker) => {
+                let build_dir = build_dir
+                    .canonicalize()
+                    .context("failed to canonicalize build dir")?;
+                std::fs::create_dir_all(target_dir).context("failed to create target dir")?;
+                let mut mounts = vec![(build_dir.as_path(), false), (target_dir, false)];
+                let source_dirs = match crate_source {
+                    CrateSource::Registry { .. } => vec![],
+                    CrateSource::ManifestPath { manifest } => {
+                        self.local_source_dirs(&manifest.path, &build_dir, &metadata)?
+                    }
+                };
+                mounts.extend(source_dirs.iter().map(|dir| (dir.as_path(), true)));
+                let target_spec_dir = match &self.target_spec {
+                    Some(target_spec) => target_spec
+                        .canonicalize()
+                        .ok()
+                        .and_then(|path| path.parent().map(Path::to_path_buf)),
+                    None => None,
+                };
+                if let Some(target_spec_dir) = &target_spec_dir {
+                    mounts.push((target_spec_dir, true));
+                }
+                let patch_dirs = self
+                    .patches
+                    .iter()
+                    .filter_map(|(_, path)| path.canonicalize().ok())
+                    .collect::<Vec<_>>();
+                mounts.extend(patch_dirs.iter().map(|dir| (dir.as_path(), true)));
+                docker.cargo_command(&build_dir, &mounts, &env)?
+            }
+        };
+        cmd.stdout(std::process::Stdio::null()) // Don't pollute output
             .stderr(stderr)
             .arg("doc")
             .arg("--manifest-path")
@@ -109,18 +338,36 @@ impl RustdocCommand {
         if !self.deps {
             cmd.arg("--no-deps");
         }
+        if crate_data.build_std {
+            cmd.arg("-Zbuild-std");
+        }
+        if let Some(target_spec) = &self.target_spec {
+            // The placeholder manifest is elsewhere, so don't rely on the working directory.
+            let target_spec = target_spec.canonicalize().with_context(|| {
+                format!("failed to find target spec file {}", target_spec.display())
+            })?;
+            cmd.arg("--target").arg(target_spec);
+        }
         if config.is_stderr_tty() {
             cmd.arg("--color=always");
         }
 
-        let output = cmd.output()?;
+        let output = run_until_deadline(&mut cmd, self.deadline)?;
+        if forward_captured {
+            config.shell_stderr().write_all(&output.stderr)?;
+        }
         if !output.status.success() {
-            if self.silence {
-                anyhow::bail!(
+            if silence {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let err = anyhow::anyhow!(
                     "Failed when running cargo-doc on {}:\n{}",
                     placeholder_manifest_path.display(),
-                    String::from_utf8_lossy(&output.stderr)
-                )
+                    stderr,
+                );
+                return Err(match registry_auth_failure_hint(&stderr) {
+                    Some(hint) => err.context(hint),
+                    None => err,
+                });
             } else {
                 anyhow::bail!(
                     "Failed when running cargo-doc on {}. See stderr.",
@@ -146,7 +393,7 @@ impl RustdocCommand {
             let lib_name = lib_target.name.as_str();
             let rustdoc_json_file_name = lib_name.replace('-', "_");
 
-            let json_path = target_dir.join(format!("doc/{rustdoc_json_file_name}.json"));
+            let json_path = doc_dir.join(format!("{rustdoc_json_file_name}.json"));
             if json_path.exists() {
                 return Ok(json_path);
             } else {
@@ -166,7 +413,7 @@ impl RustdocCommand {
             let bin_name = bin_target.name.as_str();
             let rustdoc_json_file_name = bin_name.replace('-', "_");
 
-            let json_path = target_dir.join(format!("doc/{rustdoc_json_file_name}.json"));
+            let json_path = doc_dir.join(format!("{rustdoc_json_file_name}.json"));
             if json_path.exists() {
                 return Ok(json_path);
             } else {
@@ -182,17 +429,185 @@ impl RustdocCommand {
     }
 }
 
+/// Where the container's cargo home is mounted when generating rustdoc in Docker.
+const CONTAINER_CARGO_HOME: &str = "/cargo-home";
+
+/// Run `cargo doc` in a Docker container instead of on the host,
+/// so that the generated rustdoc doesn't depend on the host's toolchain and configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DockerBuild {
+    /// The image to run, which must provide `cargo` with a toolchain
+    /// whose rustdoc JSON format matches the host's.
+    pub(crate) image: String,
+    /// Host directory used as the container's cargo home, so that the registry index
+    /// and downloaded crates are reused between runs. Defaults to a directory
+    /// next to the placeholder build directories.
+    pub(crate) cargo_home: Option<PathBuf>,
+}
+
+impl DockerBuild {
+    /// A path-safe suffix distinguishing cached rustdoc generated in this image.
+    pub(crate) fn cache_suffix(&self) -> String {
+        format!("-docker-{}", crate::util::slugify(&self.image))
+    }
+
+    /// A `docker run` command that runs `cargo` in the image.
+    ///
+    /// The given host directories are mounted at the same paths in the container,
+    /// so that paths in arguments and manifests mean the same thing in both.
+    /// The first one is used as the working directory.
+    fn cargo_command(
+        &self,
+        build_dir: &Path,
+        mounts: &[(&Path, bool)],
+        env: &[(&str, &str)],
+    ) -> anyhow::Result<Command> {
+        let cargo_home = match &self.cargo_home {
+            Some(cargo_home) => cargo_home.clone(),
+            None => build_dir.with_file_name("docker-cargo-home"),
+        };
+        std::fs::create_dir_all(&cargo_home).context("failed to create docker cargo home")?;
+        let cargo_home = cargo_home
+            .canonicalize()
+            .context("failed to canonicalize docker cargo home")?;
+
+        let mut cmd = Command::new("docker");
+        cmd.args(["run", "--rm"]);
+        // Run as the owner of the build dir, so that the generated files
+        // can be read and cleaned up without root privileges.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            let build_dir_metadata =
+                std::fs::metadata(build_dir).context("failed to read build dir metadata")?;
+            cmd.arg("--user").arg(format!(
+                "{}:{}",
+                build_dir_metadata.uid(),
+                build_dir_metadata.gid()
+            ));
+        }
+        cmd.arg("--volume")
+            .arg(format!("{}:{CONTAINER_CARGO_HOME}", cargo_home.display()));
+        let mut mounted = std::collections::HashSet::new();
+        for (dir, read_only) in mounts {
+            // Docker rejects mounting the same directory twice, e.g. a patched dependency
+            // that is also a path dependency.
+            if !mounted.insert(*dir) {
+                continue;
+            }
+            let options = if *read_only { ":ro" } else { "" };
+            cmd.arg("--volume")
+                .arg(format!("{0}:{0}{options}", dir.display()));
+        }
+        cmd.arg("--env")
+            .arg(format!("CARGO_HOME={CONTAINER_CARGO_HOME}"))
+            .arg("--env")
+            .arg(format!("HOME={CONTAINER_CARGO_HOME}"));
+        for (key, value) in env {
+            // Only the name goes on docker's command line, where other users could read it.
+            // Docker takes the value from its own environment.
+            cmd.arg("--env").arg(key).env(key, value);
+        }
+        cmd.arg("--workdir")
+            .arg(build_dir)
+            .arg(&self.image)
+            .arg("cargo");
+        Ok(cmd)
+    }
+}
+
 impl Default for RustdocCommand {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Runs the command to completion like [`Command::output()`], but kills it
+/// and returns an error if it's still running when the deadline passes.
+///
+/// Stdout is not captured, since `cargo doc` is always run with it silenced.
+fn run_until_deadline(cmd: &mut Command, deadline: Option<Instant>) -> anyhow::Result<Output> {
+    let deadline = match deadline {
+        Some(deadline) => deadline,
+        None => return Ok(cmd.output()?),
+    };
+
+    let mut child = cmd.spawn()?;
+    // Drain stderr on a separate thread, so the child can't block on a full pipe
+    // while we're waiting for it to exit.
+    let stderr_reader = child.stderr.take().map(|mut stderr| {
+        std::thread::spawn(move || {
+            let mut buf = vec![];
+            stderr.read_to_end(&mut buf).map(|_| buf)
+        })
+    });
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            anyhow::bail!("cargo-doc did not finish within the time limit and was stopped");
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    };
+
+    let stderr = match stderr_reader {
+        Some(reader) => reader.join().expect("stderr reader thread panicked")?,
+        None => vec![],
+    };
+    Ok(Output {
+        status,
+        stdout: vec![],
+        stderr,
+    })
+}
+
+/// Cargo's own error messages when it fails to authenticate with a registry
+/// are easy to miss among the rest of its output, so point the user at the fix.
+///
+/// Returns `None` if the cargo error output doesn't look like an authentication failure.
+fn registry_auth_failure_hint(cargo_stderr: &str) -> Option<String> {
+    const AUTH_FAILURE_PATTERNS: [&str; 6] = [
+        "no token found",
+        "token rejected",
+        "failed to authenticate",
+        "authentication required",
+        "got 401",
+        "got 403",
+    ];
+    if !AUTH_FAILURE_PATTERNS
+        .iter()
+        .any(|pattern| cargo_stderr.contains(pattern))
+    {
+        return None;
+    }
+
+    // Cargo usually names the registry in its suggestion, e.g.
+    // "please run `cargo login --registry my-registry`".
+    let registry = cargo_stderr
+        .split("--registry ")
+        .nth(1)
+        .and_then(|rest| {
+            rest.split(|c: char| c.is_whitespace() || c == '`')
+                .next()
+                .filter(|name| !name.is_empty())
+        })
+        .unwrap_or("<NAME>");
+    Some(format!(
+        "authentication with the registry failed; credentials may be missing or expired. \
+        Run `cargo login --registry {registry}` and try again."
+    ))
+}
+
 /// To get the rustdoc of the project, we first create a placeholder project somewhere
 /// with the project as a dependency, and run `cargo rustdoc` on it.
 fn create_placeholder_rustdoc_manifest(
     crate_source: &CrateSource,
-    _crate_data: &CrateDataForRustdoc, // TODO: use this to select crate features to enable
+    crate_data: &CrateDataForRustdoc,
+    patches: &[(String, PathBuf)],
 ) -> anyhow::Result<cargo_toml::Manifest<()>> {
     use cargo_toml::*;
 
@@ -212,12 +627,17 @@ fn create_placeholder_rustdoc_manifest(
         },
         dependencies: {
             let project_with_features: DependencyDetail = match crate_source {
-                CrateSource::Registry { crate_ } => DependencyDetail {
+                CrateSource::Registry {
+                    crate_,
+                    registry_index,
+                } => DependencyDetail {
                     // We need the *exact* version as a dependency, or else cargo will
                     // give us the latest semver-compatible version which is not we want.
                     // Fixes: https://github.com/obi1kenobi/cargo-semver-checks/issues/261
                     version: Some(format!("={}", crate_.version())),
-                    features: crate_source.all_features(),
+                    registry_index: registry_index.map(str::to_owned),
+                    features: crate_data.features.features(crate_source),
+                    default_features: crate_data.features.default_features(),
                     ..DependencyDetail::default()
                 },
                 CrateSource::ManifestPath { manifest } => DependencyDetail {
@@ -233,7 +653,8 @@ fn create_placeholder_rustdoc_manifest(
                             .context("manifest path is not valid UTF-8")?
                             .to_string()
                     }),
-                    features: crate_source.all_features(),
+                    features: crate_data.features.features(crate_source),
+                    default_features: crate_data.features.default_features(),
                     ..DependencyDetail::default()
                 },
             };
@@ -244,6 +665,31 @@ fn create_placeholder_rustdoc_manifest(
             );
             deps
         },
+        patch: {
+            let mut patch = PatchSet::new();
+            if !patches.is_empty() {
+                let mut deps = DepsSet::new();
+                for (name, path) in patches {
+                    let path = path
+                        .canonicalize()
+                        .with_context(|| {
+                            format!("failed to find override for `{name}` at {}", path.display())
+                        })?
+                        .to_str()
+                        .context("dependency override path is not valid UTF-8")?
+                        .to_string();
+                    deps.insert(
+                        name.clone(),
+                        Dependency::Detailed(DependencyDetail {
+                            path: Some(path),
+                            ..DependencyDetail::default()
+                        }),
+                    );
+                }
+                patch.insert("crates-io".to_string(), deps);
+            }
+            patch
+        },
         ..Default::default()
     })
 }
@@ -267,3 +713,124 @@ fn save_placeholder_rustdoc_manifest(
         .context("failed to create empty lib.rs")?;
     Ok(placeholder_manifest_path)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{registry_auth_failure_hint, DockerBuild, RustdocCommand};
+
+    #[test]
+    fn auth_failure_hint_names_registry() {
+        let stderr = "error: failed to download `foo v1.0.0`\n\nCaused by:\n  \
+            no token found for `my-registry`, please run `cargo login --registry my-registry`";
+        let hint = registry_auth_failure_hint(stderr).expect("no hint for auth failure");
+        assert!(
+            hint.contains("`cargo login --registry my-registry`"),
+            "{hint}"
+        );
+    }
+
+    #[test]
+    fn auth_failure_hint_without_registry_name() {
+        let stderr = "error: failed to get successful HTTP response from \
+            `https://example.com/index/config.json`, got 401";
+        let hint = registry_auth_failure_hint(stderr).expect("no hint for auth failure");
+        assert!(hint.contains("`cargo login --registry <NAME>`"), "{hint}");
+    }
+
+    #[test]
+    fn no_auth_failure_hint_for_other_errors() {
+        let stderr = "error[E0425]: cannot find value `x` in this scope";
+        assert_eq!(registry_auth_failure_hint(stderr), None);
+    }
+
+    #[test]
+    fn env_cache_suffix_depends_on_env() {
+        let with_env = |vars: &[(&str, &str)]| {
+            let env = vars
+                .iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect();
+            RustdocCommand::new().env(env).env_cache_suffix()
+        };
+        assert_eq!(with_env(&[]), "");
+        let cfg = with_env(&[("RUSTFLAGS", "--cfg foo")]);
+        assert!(cfg.starts_with("-env-"), "{cfg}");
+        assert!(!cfg.contains("foo"), "{cfg}");
+        assert_eq!(cfg, with_env(&[("RUSTFLAGS", "--cfg foo")]));
+        assert_ne!(cfg, with_env(&[("RUSTFLAGS", "--cfg bar")]));
+    }
+
+    #[test]
+    fn target_name_from_cargo_build_target() {
+        let env = [(
+            "CARGO_BUILD_TARGET".to_string(),
+            "thumbv7em-none-eabihf".to_string(),
+        )]
+        .into_iter()
+        .collect();
+        let cmd = RustdocCommand::new().env(env);
+        assert_eq!(cmd.target_name().as_deref(), Some("thumbv7em-none-eabihf"));
+
+        let env = [(
+            "CARGO_BUILD_TARGET".to_string(),
+            "specs/my-target.json".to_string(),
+        )]
+        .into_iter()
+        .collect();
+        let cmd = RustdocCommand::new().env(env);
+        assert_eq!(cmd.target_name().as_deref(), Some("my-target"));
+    }
+
+    #[test]
+    fn target_spec_takes_precedence_over_cargo_build_target() {
+        let env = [(
+            "CARGO_BUILD_TARGET".to_string(),
+            "thumbv7em-none-eabihf".to_string(),
+        )]
+        .into_iter()
+        .collect();
+        let cmd = RustdocCommand::new()
+            .env(env)
+            .target_spec(Some("specs/my-target.json".into()));
+        assert_eq!(cmd.target_name().as_deref(), Some("my-target"));
+    }
+
+    #[test]
+    fn docker_cargo_command_mounts_dirs_at_same_path() {
+        let build_dir = Path::new("test_crates/trait_missing/new")
+            .canonicalize()
+            .unwrap();
+        let cargo_home = std::env::temp_dir().join("semver-checks-docker-cargo-home");
+        let docker = DockerBuild {
+            image: "rust:1.69".to_string(),
+            cargo_home: Some(cargo_home.clone()),
+        };
+        let cmd = docker
+            .cargo_command(
+                &build_dir,
+                &[(build_dir.as_path(), false)],
+                &[("RUSTC_BOOTSTRAP", "1")],
+            )
+            .unwrap();
+
+        assert_eq!(cmd.get_program(), "docker");
+        let args: Vec<_> = cmd
+            .get_args()
+            .map(|arg| arg.to_string_lossy().into_owned())
+            .collect();
+        let build_dir = build_dir.display().to_string();
+        let cargo_home = cargo_home.canonicalize().unwrap().display().to_string();
+        assert!(
+            args.contains(&format!("{build_dir}:{build_dir}")),
+            "{args:?}"
+        );
+        assert!(
+            args.contains(&format!("{cargo_home}:/cargo-home")),
+            "{args:?}"
+        );
+        assert!(args.contains(&"RUSTC_BOOTSTRAP=1".to_string()), "{args:?}");
+        assert_eq!(args[args.len() - 2..], ["rust:1.69", "cargo"]);
+    }
+}
@@ -1,31 +1,38 @@
 use std::path::PathBuf;
+use std::time::Instant;
 
 use anyhow::Context;
 use crates_index::Crate;
 use itertools::Itertools;
 
 use crate::manifest::Manifest;
-use crate::rustdoc_cmd::RustdocCommand;
+use crate::rustdoc_cmd::{DockerBuild, RustdocCommand};
 use crate::util::slugify;
 use crate::GlobalConfig;
 
 #[derive(Debug, Clone)]
 pub(crate) enum CrateSource<'a> {
-    Registry { crate_: &'a crates_index::Version },
-    ManifestPath { manifest: &'a Manifest },
+    Registry {
+        crate_: &'a crates_index::Version,
+        /// The index URL of the registry, if not the default one.
+        registry_index: Option<&'a str>,
+    },
+    ManifestPath {
+        manifest: &'a Manifest,
+    },
 }
 
 impl<'a> CrateSource<'a> {
     pub(crate) fn name(&self) -> anyhow::Result<&str> {
         Ok(match self {
-            Self::Registry { crate_ } => crate_.name(),
+            Self::Registry { crate_, .. } => crate_.name(),
             Self::ManifestPath { manifest } => crate::manifest::get_package_name(manifest)?,
         })
     }
 
     pub(crate) fn version(&self) -> anyhow::Result<&str> {
         Ok(match self {
-            Self::Registry { crate_ } => crate_.version(),
+            Self::Registry { crate_, .. } => crate_.version(),
             Self::ManifestPath { manifest } => crate::manifest::get_package_version(manifest)?,
         })
     }
@@ -35,8 +42,15 @@ impl<'a> CrateSource<'a> {
         Ok(format!(
             "{}-{}-{}",
             match self {
-                CrateSource::Registry { .. } => "registry",
-                CrateSource::ManifestPath { .. } => "local",
+                CrateSource::Registry {
+                    registry_index: None,
+                    ..
+                } => "registry".to_string(),
+                CrateSource::Registry {
+                    registry_index: Some(url),
+                    ..
+                } => format!("registry-{}", slugify(url)),
+                CrateSource::ManifestPath { .. } => "local".to_string(),
             },
             slugify(self.name()?),
             slugify(self.version()?)
@@ -47,7 +61,7 @@ impl<'a> CrateSource<'a> {
     /// <https://doc.rust-lang.org/cargo/reference/features.html#the-features-section>
     pub(crate) fn regular_features(&self) -> Vec<String> {
         match self {
-            Self::Registry { crate_ } => crate_.features().keys().cloned().collect(),
+            Self::Registry { crate_, .. } => crate_.features().keys().cloned().collect(),
             Self::ManifestPath { manifest } => manifest.parsed.features.keys().cloned().collect(),
         }
     }
@@ -56,7 +70,7 @@ impl<'a> CrateSource<'a> {
     /// <https://doc.rust-lang.org/cargo/reference/features.html#optional-dependencies>
     pub(crate) fn implicit_features(&self) -> std::collections::BTreeSet<String> {
         let mut implicit_features: std::collections::BTreeSet<_> = match self {
-            Self::Registry { crate_ } => crate_
+            Self::Registry { crate_, .. } => crate_
                 .dependencies()
                 .iter()
                 .filter_map(|dep| dep.is_optional().then_some(dep.name()))
@@ -69,7 +83,7 @@ impl<'a> CrateSource<'a> {
                     // This part is not relevant to `Self::Registry`, because
                     // it doesn't have a `target` field and doesn't differentiate dependencies
                     // between different targets.
-                    dependencies.extend(target.dependencies.clone().into_iter());
+                    dependencies.extend(target.dependencies.clone());
                 }
                 dependencies
                     .iter()
@@ -80,7 +94,7 @@ impl<'a> CrateSource<'a> {
         };
 
         let feature_defns: Vec<&String> = match self {
-            Self::Registry { crate_ } => crate_.features().values().flatten().collect(),
+            Self::Registry { crate_, .. } => crate_.features().values().flatten().collect(),
             Self::ManifestPath { manifest } => {
                 manifest.parsed.features.values().flatten().collect()
             }
@@ -123,11 +137,68 @@ pub(crate) enum CrateType<'a> {
     },
 }
 
+/// Which features of the crate to enable when generating its rustdoc.
+#[derive(Debug, Clone)]
+pub(crate) enum FeaturesToEnable<'a> {
+    /// All features of the crate, including the implicit ones from optional dependencies.
+    All,
+    /// Only the listed features, plus the default ones if `default_features` is set.
+    Selected {
+        features: &'a [String],
+        default_features: bool,
+    },
+}
+
+impl<'a> FeaturesToEnable<'a> {
+    /// The features to enable on the crate's dependency entry in the placeholder manifest.
+    pub(crate) fn features(&self, crate_source: &CrateSource) -> Vec<String> {
+        match self {
+            Self::All => crate_source.all_features(),
+            Self::Selected { features, .. } => features.to_vec(),
+        }
+    }
+
+    /// Whether the crate's default features should be enabled.
+    pub(crate) fn default_features(&self) -> bool {
+        match self {
+            Self::All => true,
+            Self::Selected {
+                default_features, ..
+            } => *default_features,
+        }
+    }
+
+    /// A path-safe suffix distinguishing cached rustdoc generated with these features
+    /// from rustdoc generated with the default of all features enabled.
+    fn cache_suffix(&self) -> String {
+        match self {
+            Self::All => String::new(),
+            Self::Selected {
+                features,
+                default_features,
+            } => {
+                let mut features = features.to_vec();
+                features.sort();
+                features.dedup();
+                format!(
+                    "-features-{}{}",
+                    slugify(&features.join("+")),
+                    if *default_features { "" } else { "-no-default" }
+                )
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct CrateDataForRustdoc<'a> {
     pub(crate) crate_type: CrateType<'a>,
     pub(crate) name: &'a str,
-    // TODO: pass an enum describing which features to enable
+    pub(crate) features: FeaturesToEnable<'a>,
+    /// Whether to build the standard library from source with `-Z build-std`.
+    pub(crate) build_std: bool,
+    /// Whether to generate the rustdoc in a Docker container instead of on the host.
+    pub(crate) docker: Option<&'a DockerBuild>,
 }
 
 impl<'a> CrateType<'a> {
@@ -151,13 +222,25 @@ fn generate_rustdoc(
     let crate_identifier = crate_source.slug()?;
 
     let (cache_dir, cached_rustdoc) = match crate_source {
-        CrateSource::Registry { .. } => {
+        CrateSource::Registry { .. } if !rustdoc_cmd.has_patches() => {
             let cache_dir = target_root.join("cache");
-            let cached_rustdoc = cache_dir.join(format!("{crate_identifier}.json"));
+            let target_suffix = rustdoc_cmd
+                .target_name()
+                .map(|target_name| format!("-target-{}", slugify(&target_name)))
+                .unwrap_or_default();
+            let docker_suffix = crate_data
+                .docker
+                .map(DockerBuild::cache_suffix)
+                .unwrap_or_default();
+            let cached_rustdoc = cache_dir.join(format!(
+                "{crate_identifier}{}{target_suffix}{docker_suffix}{}.json",
+                crate_data.features.cache_suffix(),
+                rustdoc_cmd.env_cache_suffix(),
+            ));
 
             // We assume that the generated rustdoc is untouched.
-            // Users should run cargo-clean if they experience any anomalies.
-            if cached_rustdoc.exists() {
+            // Users should run cargo-clean or pass `--no-cache` if they experience any anomalies.
+            if rustdoc_cmd.is_cache_enabled() && cached_rustdoc.exists() {
                 config.shell_status(
                     "Parsing",
                     format_args!(
@@ -170,6 +253,11 @@ fn generate_rustdoc(
 
             (Some(cache_dir), Some(cached_rustdoc))
         }
+        CrateSource::Registry { .. } => {
+            // Overridden dependencies live on the local filesystem and may change at any time,
+            // so rustdoc generated with them is neither read from nor saved to the cache.
+            (None, None)
+        }
         CrateSource::ManifestPath { .. } => {
             // Manifest-based crates cannot be cached since they correspond
             // to a specific (and unknown) gitrev and git state which is not part of their slug.
@@ -188,7 +276,7 @@ fn generate_rustdoc(
         rustdoc_cmd.generate_rustdoc(config, build_dir.clone(), &crate_source, &crate_data)?;
 
     match crate_source {
-        CrateSource::Registry { .. } => {
+        CrateSource::Registry { .. } if !rustdoc_cmd.has_patches() => {
             // Clean up after ourselves.
             let cache_dir = cache_dir.expect(
                 "when crate_source is Registry a cache_dir was created, so it should be Some",
@@ -202,7 +290,7 @@ fn generate_rustdoc(
 
             Ok(cached_rustdoc)
         }
-        CrateSource::ManifestPath { .. } => {
+        CrateSource::Registry { .. } | CrateSource::ManifestPath { .. } => {
             // We don't do any caching here -- since the crate is saved locally,
             // it could be modified by the user after it was cached.
             Ok(rustdoc_path)
@@ -210,7 +298,8 @@ fn generate_rustdoc(
     }
 }
 
-pub(crate) trait RustdocGenerator {
+/// Generators are shared between the threads that generate rustdoc for several packages.
+pub(crate) trait RustdocGenerator: Sync {
     fn load_rustdoc(
         &self,
         config: &mut GlobalConfig,
@@ -228,6 +317,44 @@ impl RustdocFromFile {
     pub(crate) fn new(path: PathBuf) -> Self {
         Self { path }
     }
+
+    /// Download the rustdoc file at the given URL into `target_root` with `curl`,
+    /// giving up at `deadline`.
+    pub(crate) fn download(
+        url: &str,
+        target_root: &std::path::Path,
+        deadline: Option<Instant>,
+        config: &mut GlobalConfig,
+    ) -> anyhow::Result<Self> {
+        config.shell_status("Downloading", url)?;
+        let download_dir = target_root.join("remote");
+        std::fs::create_dir_all(&download_dir).context("failed to create download dir")?;
+        let path = download_dir.join(format!("{}.json", slugify(url)));
+
+        retry_network_operation(
+            config,
+            &format!("download rustdoc from {url}"),
+            || {
+                let output = std::process::Command::new("curl")
+                    .args(["--fail", "--silent", "--show-error", "--location"])
+                    .args(curl_timeout_args(deadline)?)
+                    .arg("--output")
+                    .arg(&path)
+                    .arg(url)
+                    .output()
+                    .context("failed to run curl, is it installed?")?;
+                if !output.status.success() {
+                    anyhow::bail!(
+                        "failed to download rustdoc from {url}: {}",
+                        String::from_utf8_lossy(&output.stderr).trim_end()
+                    );
+                }
+                Ok(())
+            },
+            |err| is_network_failure_message(&err.to_string()),
+        )?;
+        Ok(Self { path })
+    }
 }
 
 impl RustdocGenerator for RustdocFromFile {
@@ -394,6 +521,168 @@ impl RustdocGenerator for RustdocFromGitRevision {
     }
 }
 
+/// The source of a commit of a GitHub repository, downloaded without cloning the repository.
+#[derive(Debug)]
+pub(crate) struct RustdocFromGitHub {
+    path: RustdocFromProjectRoot,
+}
+
+impl RustdocFromGitHub {
+    /// Download the source archive of the commit with `curl` and extract it with `tar`.
+    ///
+    /// If the `GITHUB_TOKEN` environment variable is set, it's used to authenticate,
+    /// so that private repositories can be downloaded too. The download gives up at `deadline`.
+    pub(crate) fn download(
+        owner: &str,
+        repo: &str,
+        sha: &str,
+        target: &std::path::Path,
+        deadline: Option<Instant>,
+        config: &mut GlobalConfig,
+    ) -> anyhow::Result<Self> {
+        let url = format!("https://api.github.com/repos/{owner}/{repo}/tarball/{sha}");
+        config.shell_status("Downloading", &url)?;
+        std::fs::create_dir_all(target).context("failed to create download dir")?;
+        let archive = target.join("source.tar.gz");
+        let token = std::env::var("GITHUB_TOKEN").ok();
+
+        retry_network_operation(
+            config,
+            &format!("download {owner}/{repo}@{sha} from GitHub"),
+            || {
+                let mut cmd = std::process::Command::new("curl");
+                cmd.args(["--fail", "--silent", "--show-error", "--location"])
+                    .args(curl_timeout_args(deadline)?)
+                    .arg("--output")
+                    .arg(&archive)
+                    .arg(&url)
+                    .stdin(std::process::Stdio::null())
+                    .stdout(std::process::Stdio::piped())
+                    .stderr(std::process::Stdio::piped());
+                if token.is_some() {
+                    // Read the header from stdin, since other users can see the command line.
+                    cmd.args(["--header", "@-"])
+                        .stdin(std::process::Stdio::piped());
+                }
+                let mut child = cmd
+                    .spawn()
+                    .context("failed to run curl, is it installed?")?;
+                if let (Some(token), Some(mut stdin)) = (&token, child.stdin.take()) {
+                    use std::io::Write;
+
+                    writeln!(stdin, "Authorization: Bearer {token}")
+                        .context("failed to pass the GitHub token to curl")?;
+                }
+                let output = child.wait_with_output().context("failed to run curl")?;
+                if !output.status.success() {
+                    anyhow::bail!(
+                        "failed to download {owner}/{repo}@{sha} from GitHub: {}",
+                        String::from_utf8_lossy(&output.stderr).trim_end()
+                    );
+                }
+                Ok(())
+            },
+            |err| is_network_failure_message(&err.to_string()),
+        )?;
+
+        // Start from an empty directory, so that no files of a previous download remain.
+        let source_dir = target.join("source");
+        if source_dir.exists() {
+            std::fs::remove_dir_all(&source_dir).context("failed to clean source dir")?;
+        }
+        std::fs::create_dir_all(&source_dir).context("failed to create source dir")?;
+        // The archive contains a single `<owner>-<repo>-<sha>` directory with the source.
+        let output = std::process::Command::new("tar")
+            .arg("--extract")
+            .arg("--gzip")
+            .arg("--strip-components=1")
+            .arg("--file")
+            .arg(&archive)
+            .arg("--directory")
+            .arg(&source_dir)
+            .output()
+            .context("failed to run tar, is it installed?")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "failed to extract the source of {owner}/{repo}@{sha}: {}",
+                String::from_utf8_lossy(&output.stderr).trim_end()
+            );
+        }
+
+        let path = RustdocFromProjectRoot::new(&source_dir, target)?;
+        Ok(Self { path })
+    }
+}
+
+impl RustdocGenerator for RustdocFromGitHub {
+    fn load_rustdoc(
+        &self,
+        config: &mut GlobalConfig,
+        rustdoc_cmd: &RustdocCommand,
+        crate_data: CrateDataForRustdoc,
+    ) -> anyhow::Result<PathBuf> {
+        self.path.load_rustdoc(config, rustdoc_cmd, crate_data)
+    }
+}
+
+/// The source of a crate in a `.zip` or `.tar.gz` archive, e.g. a release artifact.
+#[derive(Debug)]
+pub(crate) struct RustdocFromArchive {
+    path: RustdocFromProjectRoot,
+}
+
+impl RustdocFromArchive {
+    /// Extract the archive with `tar`, which detects the archive format by itself.
+    ///
+    /// Reading `.zip` archives needs bsdtar, which is the `tar` of Windows and macOS,
+    /// while GNU tar only reads tar archives.
+    pub(crate) fn extract(
+        archive: &std::path::Path,
+        target: &std::path::Path,
+        config: &mut GlobalConfig,
+    ) -> anyhow::Result<Self> {
+        config.shell_status("Extracting", archive.display())?;
+
+        // Start from an empty directory, so that no files of a previous extraction remain.
+        let source_dir = target.join("source");
+        if source_dir.exists() {
+            std::fs::remove_dir_all(&source_dir).context("failed to clean source dir")?;
+        }
+        std::fs::create_dir_all(&source_dir).context("failed to create source dir")?;
+        // Unlike GitHub's archives, these may or may not wrap the source in a directory,
+        // so it's extracted as-is: the manifests are found wherever they are.
+        let output = std::process::Command::new("tar")
+            .arg("--extract")
+            .arg("--file")
+            .arg(archive)
+            .arg("--directory")
+            .arg(&source_dir)
+            .output()
+            .context("failed to run tar, is it installed?")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "failed to extract {}: {}",
+                archive.display(),
+                String::from_utf8_lossy(&output.stderr).trim_end()
+            );
+        }
+
+        let path = RustdocFromProjectRoot::new(&source_dir, target)?;
+        Ok(Self { path })
+    }
+}
+
+impl RustdocGenerator for RustdocFromArchive {
+    fn load_rustdoc(
+        &self,
+        config: &mut GlobalConfig,
+        rustdoc_cmd: &RustdocCommand,
+        crate_data: CrateDataForRustdoc,
+    ) -> anyhow::Result<PathBuf> {
+        self.path.load_rustdoc(config, rustdoc_cmd, crate_data)
+    }
+}
+
 // From git2 crate
 #[cfg(unix)]
 fn bytes2str(b: &[u8]) -> &std::ffi::OsStr {
@@ -411,7 +700,10 @@ fn bytes2str(b: &[u8]) -> &std::ffi::OsStr {
 pub(crate) struct RustdocFromRegistry {
     target_root: PathBuf,
     version: Option<semver::Version>,
-    index: crates_index::Index,
+    /// Behind a mutex because the index isn't `Sync`. It's only locked for lookups.
+    index: std::sync::Mutex<crates_index::Index>,
+    /// The index URL of the registry, if not cargo's default one.
+    registry_index: Option<String>,
 }
 
 impl core::fmt::Debug for RustdocFromRegistry {
@@ -420,24 +712,45 @@ impl core::fmt::Debug for RustdocFromRegistry {
             .field("target_root", &self.target_root)
             .field("version", &self.version)
             .field("index", &"<elided>")
+            .field("registry_index", &self.registry_index)
             .finish()
     }
 }
 
 impl RustdocFromRegistry {
     pub fn new(target_root: &std::path::Path, config: &mut GlobalConfig) -> anyhow::Result<Self> {
-        let mut index = crates_index::Index::new_cargo_default()?;
+        let index = retry_index_operation(config, crates_index::Index::new_cargo_default)?;
+        Self::from_index(target_root, index, None, config)
+    }
+
+    /// Use the git index of the registry at the given URL, instead of cargo's default registry.
+    ///
+    /// Crates are downloaded by cargo itself, so its configuration applies to them,
+    /// including credentials and source replacement.
+    pub fn with_index_url(
+        target_root: &std::path::Path,
+        index_url: &str,
+        config: &mut GlobalConfig,
+    ) -> anyhow::Result<Self> {
+        let index = retry_index_operation(config, || crates_index::Index::from_url(index_url))
+            .with_context(|| format!("failed to open registry index {index_url}"))?;
+        Self::from_index(target_root, index, Some(index_url.to_owned()), config)
+    }
 
+    fn from_index(
+        target_root: &std::path::Path,
+        mut index: crates_index::Index,
+        registry_index: Option<String>,
+        config: &mut GlobalConfig,
+    ) -> anyhow::Result<Self> {
         config.shell_status("Updating", "index")?;
-        while need_retry(index.update())? {
-            config.shell_status("Blocking", "waiting for lock on registry index")?;
-            std::thread::sleep(REGISTRY_BACKOFF);
-        }
+        retry_index_operation(config, || index.update())?;
 
         Ok(Self {
             target_root: target_root.to_owned(),
             version: None,
-            index,
+            index: std::sync::Mutex::new(index),
+            registry_index,
         })
     }
 
@@ -503,6 +816,8 @@ impl RustdocGenerator for RustdocFromRegistry {
     ) -> anyhow::Result<PathBuf> {
         let crate_ = self
             .index
+            .lock()
+            .expect("registry index lock poisoned")
             .crate_(crate_data.name)
             .with_context(|| anyhow::format_err!("{} not found in registry", crate_data.name))?;
 
@@ -536,7 +851,10 @@ impl RustdocGenerator for RustdocFromRegistry {
             config,
             rustdoc_cmd,
             self.target_root.clone(),
-            CrateSource::Registry { crate_ },
+            CrateSource::Registry {
+                crate_,
+                registry_index: self.registry_index.as_deref(),
+            },
             crate_data,
         )
     }
@@ -544,18 +862,147 @@ impl RustdocGenerator for RustdocFromRegistry {
 
 const REGISTRY_BACKOFF: std::time::Duration = std::time::Duration::from_secs(1);
 
-/// Check if we need to retry retrieving the Index.
-fn need_retry(res: Result<(), crates_index::Error>) -> anyhow::Result<bool> {
-    match res {
-        Ok(()) => Ok(false),
-        Err(crates_index::Error::Git(err)) => {
-            if err.class() == git2::ErrorClass::Index && err.code() == git2::ErrorCode::Locked {
-                Ok(true)
-            } else {
-                Err(crates_index::Error::Git(err).into())
+/// How long to wait before each retry of a network operation that failed
+/// due to a network error. The operation fails once these are exhausted.
+const NETWORK_RETRY_DELAYS: [std::time::Duration; 3] = [
+    std::time::Duration::from_secs(2),
+    std::time::Duration::from_secs(4),
+    std::time::Duration::from_secs(8),
+];
+
+/// Run an operation on the registry index, such as cloning or updating it.
+///
+/// If the index is locked by another process, wait until it's available.
+/// If the operation fails due to what looks like a transient network error,
+/// retry it with exponential backoff.
+fn retry_index_operation<T>(
+    config: &mut GlobalConfig,
+    mut operation: impl FnMut() -> Result<T, crates_index::Error>,
+) -> anyhow::Result<T> {
+    let mut network_retry_delays = NETWORK_RETRY_DELAYS.iter();
+    loop {
+        match operation() {
+            Ok(value) => return Ok(value),
+            Err(err) if is_index_locked(&err) => {
+                config.shell_status("Blocking", "waiting for lock on registry index")?;
+                std::thread::sleep(REGISTRY_BACKOFF);
+            }
+            Err(err) if is_network_error(&err) => {
+                let Some(delay) = network_retry_delays.next() else {
+                    return Err(err.into());
+                };
+                config.shell_warn(format_args!(
+                    "failed to access registry index, retrying in {}s: {err}",
+                    delay.as_secs()
+                ))?;
+                std::thread::sleep(*delay);
             }
+            Err(err) => return Err(err.into()),
         }
-        Err(err) => Err(err.into()),
+    }
+}
+
+/// Run an operation that downloads something, such as a crate or a rustdoc file.
+///
+/// If it fails due to what looks like a transient network error, according to
+/// `is_network_error`, retry it with exponential backoff.
+pub(crate) fn retry_network_operation<T>(
+    config: &mut GlobalConfig,
+    description: &str,
+    mut operation: impl FnMut() -> anyhow::Result<T>,
+    is_network_error: impl Fn(&anyhow::Error) -> bool,
+) -> anyhow::Result<T> {
+    let mut network_retry_delays = NETWORK_RETRY_DELAYS.iter();
+    loop {
+        match operation() {
+            Ok(value) => return Ok(value),
+            Err(err) if is_network_error(&err) => {
+                let Some(delay) = network_retry_delays.next() else {
+                    return Err(err);
+                };
+                config.shell_warn(format_args!(
+                    "failed to {description}, retrying in {}s: {err:#}",
+                    delay.as_secs()
+                ))?;
+                std::thread::sleep(*delay);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// How long `curl` may take to connect before giving up, so that an unreachable host
+/// is retried rather than waited on.
+const CURL_CONNECT_TIMEOUT_SECS: u64 = 30;
+
+/// The `curl` arguments that stop a download that hangs, or that is still running
+/// at the check's `deadline`.
+fn curl_timeout_args(deadline: Option<Instant>) -> anyhow::Result<Vec<String>> {
+    let mut args = vec![
+        "--connect-timeout".to_string(),
+        CURL_CONNECT_TIMEOUT_SECS.to_string(),
+    ];
+    if let Some(deadline) = deadline {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            anyhow::bail!("the time limit was reached before the download started");
+        }
+        // curl takes whole seconds, and 0 would mean no limit.
+        args.push("--max-time".to_string());
+        args.push(remaining.as_secs().max(1).to_string());
+    }
+    Ok(args)
+}
+
+/// Check if the error output of `cargo` or `curl` describes a possibly transient
+/// network failure, like a timeout or a server error, rather than e.g. a missing crate
+/// or rejected credentials.
+pub(crate) fn is_network_failure_message(message: &str) -> bool {
+    const NETWORK_FAILURE_PATTERNS: [&str; 10] = [
+        "spurious network error",
+        "could not resolve host",
+        "couldn't resolve host",
+        "failed to connect",
+        "timed out",
+        "timeout was reached",
+        "connection reset",
+        "connection refused",
+        "returned error: 5",
+        "got 5",
+    ];
+    let message = message.to_lowercase();
+    NETWORK_FAILURE_PATTERNS
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+/// Check if the Index failed to be retrieved because another process holds its lock.
+fn is_index_locked(err: &crates_index::Error) -> bool {
+    matches!(
+        err,
+        crates_index::Error::Git(err)
+            if err.class() == git2::ErrorClass::Index && err.code() == git2::ErrorCode::Locked
+    )
+}
+
+/// Check if the Index failed to be retrieved due to a possibly transient network failure.
+fn is_network_error(err: &crates_index::Error) -> bool {
+    match err {
+        crates_index::Error::Git(err) => matches!(
+            err.class(),
+            git2::ErrorClass::Net
+                | git2::ErrorClass::Http
+                | git2::ErrorClass::Ssl
+                | git2::ErrorClass::Ssh
+        ),
+        crates_index::Error::Io(err) => matches!(
+            err.kind(),
+            std::io::ErrorKind::TimedOut
+                | std::io::ErrorKind::ConnectionRefused
+                | std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+        ),
+        _ => false,
     }
 }
 
@@ -563,7 +1010,13 @@ fn need_retry(res: Result<(), crates_index::Error>) -> anyhow::Result<bool> {
 mod tests {
     use crates_index::{Crate, Version};
 
-    use super::choose_baseline_version;
+    use std::time::{Duration, Instant};
+
+    use super::{
+        choose_baseline_version, curl_timeout_args, is_index_locked, is_network_error,
+        is_network_failure_message, RustdocFromArchive,
+    };
+    use crate::GlobalConfig;
 
     fn new_mock_version(version_name: &str, yanked: bool) -> Version {
         // `crates_index::Version` cannot be created explicitly, as all its fields
@@ -606,6 +1059,98 @@ mod tests {
         assert_eq!(chosen_baseline, expected.to_owned());
     }
 
+    #[test]
+    fn index_errors_are_classified() {
+        let locked = crates_index::Error::Git(git2::Error::new(
+            git2::ErrorCode::Locked,
+            git2::ErrorClass::Index,
+            "index is locked",
+        ));
+        assert!(is_index_locked(&locked));
+        assert!(!is_network_error(&locked));
+
+        let timeout = crates_index::Error::Git(git2::Error::new(
+            git2::ErrorCode::GenericError,
+            git2::ErrorClass::Net,
+            "failed to connect",
+        ));
+        assert!(is_network_error(&timeout));
+        assert!(!is_index_locked(&timeout));
+
+        let reset = crates_index::Error::Io(std::io::ErrorKind::ConnectionReset.into());
+        assert!(is_network_error(&reset));
+
+        let missing = crates_index::Error::Io(std::io::ErrorKind::NotFound.into());
+        assert!(!is_network_error(&missing));
+        assert!(!is_index_locked(&missing));
+    }
+
+    #[test]
+    fn network_failure_messages_are_classified() {
+        assert!(is_network_failure_message(
+            "curl: (6) Could not resolve host: example.com"
+        ));
+        assert!(is_network_failure_message(
+            "curl: (22) The requested URL returned error: 503"
+        ));
+        assert!(is_network_failure_message(
+            "error: failed to download from `https://static.crates.io/crates/foo/1.0.0/download`\n\n\
+            Caused by:\n  [28] Timeout was reached"
+        ));
+
+        assert!(!is_network_failure_message(
+            "curl: (22) The requested URL returned error: 404"
+        ));
+        assert!(!is_network_failure_message(
+            "error: no matching package named `foo` found"
+        ));
+        assert!(!is_network_failure_message(
+            "error: failed to get successful HTTP response, got 401"
+        ));
+    }
+
+    #[test]
+    fn curl_timeouts_follow_deadline() {
+        let args = curl_timeout_args(None).unwrap();
+        assert!(args.contains(&"--connect-timeout".to_string()), "{args:?}");
+        assert!(!args.contains(&"--max-time".to_string()), "{args:?}");
+
+        let args = curl_timeout_args(Some(Instant::now() + Duration::from_secs(90))).unwrap();
+        let max_time = args
+            .iter()
+            .position(|arg| arg == "--max-time")
+            .map(|index| args[index + 1].parse::<u64>().unwrap())
+            .expect("no --max-time");
+        assert!((1..=90).contains(&max_time), "{max_time}");
+
+        curl_timeout_args(Some(Instant::now())).expect_err("deadline already passed");
+    }
+
+    #[test]
+    fn archive_source_is_extracted() {
+        let dir = std::env::temp_dir().join(format!(
+            "cargo-semver-checks-archive-test-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let archive = dir.join("source.tar.gz");
+        let status = std::process::Command::new("tar")
+            .arg("--create")
+            .arg("--gzip")
+            .arg("--file")
+            .arg(&archive)
+            .args(["--directory", "./test_crates/function_missing", "old"])
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let result =
+            RustdocFromArchive::extract(&archive, &dir.join("target"), &mut GlobalConfig::new());
+        std::fs::remove_dir_all(&dir).unwrap();
+        let extracted = result.unwrap();
+        assert!(extracted.path.manifests.contains_key("function_missing"));
+    }
+
     #[test]
     fn baseline_choosing_logic_skips_yanked() {
         assert_correctly_picks_baseline_version(
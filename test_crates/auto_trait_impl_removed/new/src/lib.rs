@@ -40,3 +40,11 @@ pub struct UnpinStruct {
     bar: i64,
     _marker: PhantomPinned,
 }
+
+// Rustdoc doesn't list the auto trait impls of #[doc(hidden)] types,
+// so it's unknown whether this struct still implements any of them,
+// and it should NOT be reported. It's still all of Send, Sync, etc.
+#[doc(hidden)]
+pub struct StructBecomesHidden {
+    bar: i64,
+}
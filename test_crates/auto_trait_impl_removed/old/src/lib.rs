@@ -20,3 +20,7 @@ pub struct RefUnwindSafeStruct {
 pub struct UnpinStruct {
     bar: i64,
 }
+
+pub struct StructBecomesHidden {
+    bar: i64,
+}
@@ -0,0 +1,20 @@
+pub struct DerivedDebugStruct;
+
+pub enum ManualDebugEnum {
+    Var,
+}
+
+// The following is not a semver issue: it's not breaking to replace
+// a derived impl with a hand-impl of the same trait.
+
+pub struct ReplacedWithManualDebug;
+
+impl std::fmt::Debug for ReplacedWithManualDebug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ReplacedWithManualDebug")
+    }
+}
+
+// Types that are not public don't matter.
+
+struct PrivateStruct;
@@ -0,0 +1,24 @@
+// Removing a derived Debug is reported by derive_trait_impl_removed instead.
+#[derive(Debug)]
+pub struct DerivedDebugStruct;
+
+pub enum ManualDebugEnum {
+    Var,
+}
+
+impl std::fmt::Debug for ManualDebugEnum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ManualDebugEnum")
+    }
+}
+
+// The following is not a semver issue: it's not breaking to replace
+// a derived impl with a hand-impl of the same trait.
+
+#[derive(Debug)]
+pub struct ReplacedWithManualDebug;
+
+// Types that are not public don't matter.
+
+#[derive(Debug)]
+struct PrivateStruct;
@@ -0,0 +1,24 @@
+/// Build one with [`Builder`], or convert it with [`from_parts`].
+pub struct Documented;
+
+// `Builder` was removed, so the link to it from `Documented` is broken.
+
+pub fn from_parts() -> Documented {
+    Documented
+}
+
+/// Links to [`Kept`], which is still there.
+pub fn keeps_links() {}
+
+pub struct Kept;
+
+/// Configure it with [`Options`].
+pub struct Configured;
+
+// `Options` moved to a module, but is re-exported where it was,
+// so the link to it from `Configured` still resolves.
+pub mod options {
+    pub struct Options;
+}
+
+pub use options::Options;
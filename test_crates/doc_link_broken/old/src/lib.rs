@@ -0,0 +1,23 @@
+/// Build one with [`Builder`], or convert it with [`from_parts`].
+pub struct Documented;
+
+pub struct Builder;
+
+pub fn from_parts() -> Documented {
+    Documented
+}
+
+/// Links to [`Kept`], which is still there.
+pub fn keeps_links() {}
+
+pub struct Kept;
+
+/// Links to [`AlsoRemoved`], but is removed along with it.
+pub fn removed_with_target() {}
+
+pub struct AlsoRemoved;
+
+/// Configure it with [`Options`].
+pub struct Configured;
+
+pub struct Options;
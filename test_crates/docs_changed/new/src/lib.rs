@@ -0,0 +1,21 @@
+/// A struct, now with more details.
+pub struct DocsChanged;
+
+/// A struct that now has documentation.
+pub struct DocsAdded;
+
+pub enum DocsRemoved {
+    Variant,
+}
+
+/// Unchanged.
+pub struct DocsUnchanged;
+
+/// A function, now with more details.
+pub fn function_docs_changed() {}
+
+/// A trait, now with more details.
+pub trait TraitDocsChanged {}
+
+/// Private items still don't matter.
+struct PrivateDocsChanged;
@@ -0,0 +1,21 @@
+/// A struct.
+pub struct DocsChanged;
+
+pub struct DocsAdded;
+
+/// An enum.
+pub enum DocsRemoved {
+    Variant,
+}
+
+/// Unchanged.
+pub struct DocsUnchanged;
+
+/// A function.
+pub fn function_docs_changed() {}
+
+/// A trait.
+pub trait TraitDocsChanged {}
+
+/// Private items don't matter.
+struct PrivateDocsChanged;
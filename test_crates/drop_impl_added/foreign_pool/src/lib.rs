@@ -0,0 +1,6 @@
+/// A trait from another crate, with a method that looks like `Drop::drop`.
+pub trait Pool {
+    fn len(&self) -> usize;
+
+    fn drop(&mut self);
+}
@@ -0,0 +1,57 @@
+pub struct DropAdded {
+    pub field: String,
+}
+
+impl Drop for DropAdded {
+    fn drop(&mut self) {}
+}
+
+pub enum EnumDropAdded {
+    Var,
+}
+
+impl Drop for EnumDropAdded {
+    fn drop(&mut self) {}
+}
+
+pub struct DropUnchanged;
+
+impl Drop for DropUnchanged {
+    fn drop(&mut self) {}
+}
+
+// Types that are not public don't matter.
+
+struct PrivateStruct;
+
+impl Drop for PrivateStruct {
+    fn drop(&mut self) {}
+}
+
+// Methods named `drop` that don't come from `Drop` don't matter.
+
+pub struct InherentDropAdded;
+
+impl InherentDropAdded {
+    pub fn drop(&mut self) {}
+}
+
+pub trait Cleanup {
+    fn drop(&mut self);
+}
+
+pub struct LocalTraitDropAdded;
+
+impl Cleanup for LocalTraitDropAdded {
+    fn drop(&mut self) {}
+}
+
+pub struct ForeignTraitDropAdded;
+
+impl foreign_pool::Pool for ForeignTraitDropAdded {
+    fn len(&self) -> usize {
+        0
+    }
+
+    fn drop(&mut self) {}
+}
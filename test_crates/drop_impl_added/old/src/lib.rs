@@ -0,0 +1,29 @@
+pub struct DropAdded {
+    pub field: String,
+}
+
+pub enum EnumDropAdded {
+    Var,
+}
+
+pub struct DropUnchanged;
+
+impl Drop for DropUnchanged {
+    fn drop(&mut self) {}
+}
+
+// Types that are not public don't matter.
+
+struct PrivateStruct;
+
+// Methods named `drop` that don't come from `Drop` don't matter.
+
+pub struct InherentDropAdded;
+
+pub trait Cleanup {
+    fn drop(&mut self);
+}
+
+pub struct LocalTraitDropAdded;
+
+pub struct ForeignTraitDropAdded;
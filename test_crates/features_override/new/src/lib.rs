@@ -0,0 +1,6 @@
+#![no_std]
+
+pub fn stable_function() {}
+
+#[cfg(feature = "unstable")]
+pub fn stabilized_function() {}
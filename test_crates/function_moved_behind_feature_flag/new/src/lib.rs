@@ -0,0 +1,10 @@
+#[cfg(feature = "extra")]
+pub fn becomes_gated() {}
+
+// The following are not semver issues for this lint.
+
+#[cfg(feature = "extra")]
+pub fn already_gated() {}
+
+#[cfg(not(target_os = "nonexistent"))]
+pub fn gated_by_non_feature_cfg() {}
@@ -0,0 +1,6 @@
+pub fn becomes_gated() {}
+
+#[cfg(feature = "extra")]
+pub fn already_gated() {}
+
+pub fn gated_by_non_feature_cfg() {}
@@ -0,0 +1,25 @@
+// These functions had the #[must_use] attribute in the old version.
+// Removal of the attribute should be reported by this rule.
+
+pub fn MustUseFunctionToFunction() {}
+
+pub fn MustUseMessageFunctionToFunction() {}
+
+// These functions keep some form of the #[must_use] attribute,
+// so they should NOT be reported by this rule.
+
+#[must_use = "Foo"]
+pub fn MustUseFunctionToMustUseMessageFunction() {}
+
+#[must_use]
+pub fn MustUseMessageFunctionToMustUseFunction() {}
+
+// This function did not have the #[must_use] attribute in the old version,
+// so it should NOT be reported by this rule.
+
+#[must_use]
+pub fn FunctionToMustUseFunction() {}
+
+// This function is private, so it should NOT be reported by this rule.
+
+fn MustUsePrivateFunctionToPrivateFunction() {}
@@ -0,0 +1,16 @@
+// This function becomes #[doc(hidden)], so it should be reported by this rule.
+pub fn becomes_hidden() {}
+
+// This function was already #[doc(hidden)], so it should NOT be reported by this rule.
+#[doc(hidden)]
+pub fn already_hidden() {}
+
+// This function stops being #[doc(hidden)], so it should NOT be reported by this rule.
+#[doc(hidden)]
+pub fn becomes_visible() {}
+
+// This function gains a different doc attribute, so it should NOT be reported by this rule.
+pub fn gains_doc_alias() {}
+
+// This function is private, so it should NOT be reported by this rule.
+fn private_becomes_hidden() {}
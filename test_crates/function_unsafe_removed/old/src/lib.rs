@@ -0,0 +1,11 @@
+// This function becomes safe, so it should be reported by this rule.
+pub unsafe fn becomes_safe() {}
+
+// This function was already safe, so it should NOT be reported by this rule.
+pub fn already_safe() {}
+
+// This function stays unsafe, so it should NOT be reported by this rule.
+pub unsafe fn stays_unsafe() {}
+
+// This function is private, so it should NOT be reported by this rule.
+unsafe fn private_becomes_safe() {}
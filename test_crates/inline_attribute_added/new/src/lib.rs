@@ -0,0 +1,13 @@
+#[inline]
+pub fn inline_added() {}
+
+#[inline(always)]
+pub fn inline_always_added() {}
+
+#[inline]
+pub fn inline_unchanged() {}
+
+// Private functions don't matter.
+
+#[inline]
+fn private_inline_added() {}
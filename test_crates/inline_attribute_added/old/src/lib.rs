@@ -0,0 +1,10 @@
+pub fn inline_added() {}
+
+pub fn inline_always_added() {}
+
+#[inline]
+pub fn inline_unchanged() {}
+
+// Private functions don't matter.
+
+fn private_inline_added() {}
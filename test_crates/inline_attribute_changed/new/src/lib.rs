@@ -0,0 +1,16 @@
+pub fn inline_removed() {}
+
+pub fn inline_always_removed() {}
+
+#[inline(always)]
+pub fn inline_becomes_always() {}
+
+#[inline]
+pub fn inline_never_becomes_inline() {}
+
+#[inline]
+pub fn inline_unchanged() {}
+
+// Private functions don't matter.
+
+fn private_inline_removed() {}
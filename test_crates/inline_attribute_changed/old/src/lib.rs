@@ -0,0 +1,19 @@
+#[inline]
+pub fn inline_removed() {}
+
+#[inline(always)]
+pub fn inline_always_removed() {}
+
+#[inline]
+pub fn inline_becomes_always() {}
+
+#[inline(never)]
+pub fn inline_never_becomes_inline() {}
+
+#[inline]
+pub fn inline_unchanged() {}
+
+// Private functions don't matter.
+
+#[inline]
+fn private_inline_removed() {}
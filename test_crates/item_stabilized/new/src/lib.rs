@@ -0,0 +1,25 @@
+/// Stable since 0.2.0.
+pub fn function_stabilized() {}
+
+/// A stable API.
+pub struct StructStabilized;
+
+pub enum EnumStabilized {
+    Variant,
+}
+
+/// A trait.
+pub trait TraitStabilized {}
+
+/// Experimental: this is still experimental in the new version,
+/// so it should NOT be reported.
+pub fn still_experimental() {}
+
+pub fn docs_removed() {}
+
+/// Words that merely contain the pattern, like "unstableness" or "experimentally",
+/// should NOT be reported. Still nothing to report.
+pub fn similar_words() {}
+
+/// This private function is stable, but it should NOT be reported.
+fn private_stabilized() {}
@@ -0,0 +1,28 @@
+/// Experimental: this may change without notice.
+pub fn function_stabilized() {}
+
+/// This API is unstable.
+pub struct StructStabilized;
+
+/// **Experimental.**
+pub enum EnumStabilized {
+    Variant,
+}
+
+/// An experimental trait.
+pub trait TraitStabilized {}
+
+/// Experimental: this is still experimental in the new version,
+/// so it should NOT be reported.
+pub fn still_experimental() {}
+
+/// Experimental. The new version removes the documentation entirely,
+/// including this note, so it should be reported.
+pub fn docs_removed() {}
+
+/// Words that merely contain the pattern, like "unstableness" or "experimentally",
+/// should NOT be reported.
+pub fn similar_words() {}
+
+/// This private function is experimental, but it should NOT be reported.
+fn private_stabilized() {}
@@ -0,0 +1,35 @@
+pub trait Moved {
+    fn moved(&self);
+}
+
+pub struct Plain;
+
+impl Plain {
+    pub fn unchanged(&self) {}
+}
+
+impl Moved for Plain {
+    fn moved(&self) {}
+}
+
+// `Default` is in the prelude, so `Plain::default()` still works without any imports.
+impl Default for Plain {
+    fn default() -> Self {
+        Plain
+    }
+}
+
+pub enum Enum {
+    Variant,
+}
+
+impl Moved for Enum {
+    fn moved(&self) {}
+}
+
+// Private types don't matter.
+struct PrivateStruct;
+
+impl Moved for PrivateStruct {
+    fn moved(&self) {}
+}
@@ -0,0 +1,26 @@
+pub struct Plain;
+
+impl Plain {
+    pub fn moved(&self) {}
+
+    pub fn default() -> Self {
+        Plain
+    }
+
+    pub fn unchanged(&self) {}
+}
+
+pub enum Enum {
+    Variant,
+}
+
+impl Enum {
+    pub fn moved(&self) {}
+}
+
+// Private types don't matter.
+struct PrivateStruct;
+
+impl PrivateStruct {
+    pub fn moved(&self) {}
+}
@@ -0,0 +1,28 @@
+#![feature(negative_impls)]
+
+pub struct GainsNegativeSend;
+
+impl !Send for GainsNegativeSend {}
+
+pub trait Marker {}
+
+pub struct GainsNegativeMarker;
+
+impl !Marker for GainsNegativeMarker {}
+
+// The following types shouldn't be reported by this lint.
+
+/// Already had the negative impl.
+pub struct AlreadyNotSend;
+
+impl !Send for AlreadyNotSend {}
+
+/// Already wasn't `Sync`, since it holds a raw pointer.
+pub struct AlreadyNotSync(*const u8);
+
+impl !Sync for AlreadyNotSync {}
+
+/// Private types aren't part of the API.
+struct PrivateGainsNegativeSend;
+
+impl !Send for PrivateGainsNegativeSend {}
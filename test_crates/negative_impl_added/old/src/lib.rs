@@ -0,0 +1,20 @@
+#![feature(negative_impls)]
+
+pub struct GainsNegativeSend;
+
+pub trait Marker {}
+
+pub struct GainsNegativeMarker;
+
+// The following types shouldn't be reported by this lint.
+
+/// Already had the negative impl.
+pub struct AlreadyNotSend;
+
+impl !Send for AlreadyNotSend {}
+
+/// Already wasn't `Sync`, since it holds a raw pointer.
+pub struct AlreadyNotSync(*const u8);
+
+/// Private types aren't part of the API.
+struct PrivateGainsNegativeSend;
@@ -0,0 +1,48 @@
+#[repr(C)]
+pub struct FieldsSwapped {
+    pub second: u32,
+    pub first: u8,
+}
+
+#[repr(C)]
+pub struct PrivateFieldsSwapped {
+    second: u32,
+    first: u8,
+}
+
+#[repr(C)]
+pub struct FieldsRotated {
+    pub b: u16,
+    pub c: u32,
+    pub a: u8,
+}
+
+// This struct keeps its field order while gaining a field.
+// It should not be reported by this lint.
+#[repr(C)]
+pub struct FieldAdded {
+    pub zeroth: u64,
+    pub first: u8,
+    pub second: u32,
+}
+
+// This struct is not repr(C), so the compiler may reorder its fields anyway.
+// It should not be reported.
+pub struct NotReprC {
+    pub second: u32,
+    pub first: u8,
+}
+
+// This struct is not public. It should not be reported.
+#[repr(C)]
+struct PrivateStruct {
+    second: u32,
+    first: u8,
+}
+
+#[repr(C)]
+pub struct SingleLineSwapped { pub second: u32, pub first: u8 }
+
+// This struct keeps its field order on a single line. It should not be reported.
+#[repr(C)]
+pub struct SingleLineKept { pub first: u8, pub second: u32 }
@@ -0,0 +1,47 @@
+#[repr(C)]
+pub struct FieldsSwapped {
+    pub first: u8,
+    pub second: u32,
+}
+
+#[repr(C)]
+pub struct PrivateFieldsSwapped {
+    first: u8,
+    second: u32,
+}
+
+#[repr(C)]
+pub struct FieldsRotated {
+    pub a: u8,
+    pub b: u16,
+    pub c: u32,
+}
+
+// This struct keeps its field order while gaining a field.
+// It should not be reported by this lint.
+#[repr(C)]
+pub struct FieldAdded {
+    pub first: u8,
+    pub second: u32,
+}
+
+// This struct is not repr(C), so the compiler may reorder its fields anyway.
+// It should not be reported.
+pub struct NotReprC {
+    pub first: u8,
+    pub second: u32,
+}
+
+// This struct is not public. It should not be reported.
+#[repr(C)]
+struct PrivateStruct {
+    first: u8,
+    second: u32,
+}
+
+#[repr(C)]
+pub struct SingleLineSwapped { pub first: u8, pub second: u32 }
+
+// This struct keeps its field order on a single line. It should not be reported.
+#[repr(C)]
+pub struct SingleLineKept { pub first: u8, pub second: u32 }
@@ -0,0 +1,4 @@
+/// Unstable, despite being public.
+///
+/// cargo:semver-exempt
+pub struct ExemptStruct {}
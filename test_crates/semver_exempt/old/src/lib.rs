@@ -0,0 +1,23 @@
+/// Unstable, despite being public.
+///
+/// cargo:semver-exempt
+pub struct ExemptStruct {
+    pub field: i64,
+}
+
+impl ExemptStruct {
+    pub fn method(&self) {}
+}
+
+/// cargo:semver-exempt
+pub fn exempt_function() {}
+
+/// cargo:semver-exempt
+pub trait ExemptTrait {}
+
+// These items are not exempt, so their removal should be reported.
+pub struct RegularStruct {
+    pub field: i64,
+}
+
+pub fn regular_function() {}
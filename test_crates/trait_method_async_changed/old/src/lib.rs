@@ -0,0 +1,24 @@
+#![feature(async_fn_in_trait)]
+#![allow(incomplete_features)]
+
+use std::future::Future;
+use std::pin::Pin;
+
+pub trait Service {
+    /// The form generated by the `async-trait` macro, migrated to a native async fn.
+    fn migrated_to_native<'a>(&'a self) -> Pin<Box<dyn Future<Output = u8> + Send + 'a>>;
+
+    /// A native async fn, migrated to the `async-trait` form.
+    async fn migrated_to_boxed(&self) -> u8;
+
+    // The following methods shouldn't be reported by this lint.
+
+    async fn stays_async(&self) -> u8;
+
+    fn stays_boxed<'a>(&'a self) -> Pin<Box<dyn Future<Output = u8> + Send + 'a>>;
+}
+
+/// Private traits can't be implemented downstream, so they shouldn't be reported.
+trait PrivateService {
+    async fn migrated_to_boxed(&self) -> u8;
+}
@@ -0,0 +1,17 @@
+pub trait RenamedParam {
+    fn renamed(&self, value: i64);
+
+    fn renamed_with_default(&self, value: i64, scale: u8) {}
+
+    fn unchanged(&self, value: i64);
+}
+
+pub trait CountChanged {
+    // Changing the number of parameters is a different lint's concern.
+    fn takes_more(&self, value: i64);
+}
+
+// Private traits don't matter.
+trait PrivateRenamedParam {
+    fn renamed(&self, value: i64);
+}
@@ -0,0 +1,20 @@
+pub trait Trait {
+    // These methods change their parameter count, so they should be reported by this rule.
+    fn gains_parameter(&self);
+
+    fn loses_parameter(&self, x: i64);
+
+    fn becomes_associated_fn(&self);
+
+    fn provided_gains_parameter(&self) {}
+
+    // These methods keep their parameter count, so they should NOT be reported by this rule.
+    fn unchanged(&self, x: i64);
+
+    fn parameter_renamed(&self, x: i64);
+}
+
+// This trait is private, so its methods should NOT be reported by this rule.
+trait PrivateTrait {
+    fn gains_parameter(&self);
+}
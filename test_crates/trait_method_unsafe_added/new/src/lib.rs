@@ -0,0 +1,18 @@
+pub trait Trait {
+    // This method becomes unsafe, so it should be reported by this rule.
+    unsafe fn becomes_unsafe(&self);
+
+    // This provided method becomes unsafe, so it should be reported by this rule.
+    unsafe fn provided_becomes_unsafe(&self) {}
+
+    // This method was already unsafe, so it should NOT be reported by this rule.
+    unsafe fn already_unsafe(&self);
+
+    // This method becomes safe, so it should NOT be reported by this rule.
+    fn becomes_safe(&self);
+}
+
+// This trait is private, so its methods should NOT be reported by this rule.
+trait PrivateTrait {
+    unsafe fn becomes_unsafe(&self);
+}
@@ -0,0 +1,10 @@
+#[cfg(feature = "extra")]
+pub trait BecomesGated {}
+
+// The following are not semver issues for this lint.
+
+#[cfg(feature = "extra")]
+pub trait AlreadyGated {}
+
+#[cfg(not(target_os = "nonexistent"))]
+pub trait GatedByNonFeatureCfg {}
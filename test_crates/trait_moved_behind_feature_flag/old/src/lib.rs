@@ -0,0 +1,6 @@
+pub trait BecomesGated {}
+
+#[cfg(feature = "extra")]
+pub trait AlreadyGated {}
+
+pub trait GatedByNonFeatureCfg {}
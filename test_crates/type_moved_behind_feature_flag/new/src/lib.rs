@@ -0,0 +1,18 @@
+#[cfg(feature = "extra")]
+pub struct StructBecomesGated;
+
+#[cfg(all(feature = "extra", not(feature = "nonexistent")))]
+pub enum EnumBecomesGated {
+    Variant,
+}
+
+// The following are not semver issues for this lint.
+
+#[cfg(feature = "extra")]
+pub struct AlreadyGated;
+
+#[cfg(not(target_os = "nonexistent"))]
+pub struct GatedByNonFeatureCfg;
+
+#[cfg(not(feature = "nonexistent"))]
+pub struct GatedByNegatedFeature;
@@ -0,0 +1,12 @@
+pub struct StructBecomesGated;
+
+pub enum EnumBecomesGated {
+    Variant,
+}
+
+#[cfg(feature = "extra")]
+pub struct AlreadyGated;
+
+pub struct GatedByNonFeatureCfg;
+
+pub struct GatedByNegatedFeature;
@@ -0,0 +1,25 @@
+// These types had the #[must_use] attribute in the old version.
+// Removal of the attribute should be reported by this rule.
+
+#[must_use]
+pub struct MustUseStructToStruct;
+
+#[must_use = "Foo"]
+pub enum MustUseMessageEnumToEnum {
+    Bar,
+}
+
+// This type keeps the #[must_use] attribute, so it should NOT be reported by this rule.
+
+#[must_use]
+pub struct MustUseStructToMustUseMessageStruct;
+
+// This type did not have the #[must_use] attribute in the old version,
+// so it should NOT be reported by this rule.
+
+pub struct StructToMustUseStruct;
+
+// This type is private, so it should NOT be reported by this rule.
+
+#[must_use]
+struct MustUsePrivateStructToPrivateStruct;
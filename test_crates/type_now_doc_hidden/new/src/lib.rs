@@ -0,0 +1,19 @@
+// These types become #[doc(hidden)], so they should be reported by this rule.
+#[doc(hidden)]
+pub struct StructBecomesHidden;
+
+#[doc(hidden)]
+pub enum EnumBecomesHidden {
+    Variant,
+}
+
+// This type was already #[doc(hidden)], so it should NOT be reported by this rule.
+#[doc(hidden)]
+pub struct AlreadyHidden;
+
+// This type stops being #[doc(hidden)], so it should NOT be reported by this rule.
+pub struct BecomesVisible;
+
+// This type is private, so it should NOT be reported by this rule.
+#[doc(hidden)]
+struct PrivateBecomesHidden;
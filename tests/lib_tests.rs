@@ -1,4 +1,10 @@
-use cargo_semver_checks::{ActualSemverUpdate, Check, ReleaseType, Rustdoc};
+use cargo_semver_checks::{
+    ActualSemverUpdate, Check, CheckError, CrateReport, FeatureOverride, JunitReporter, LintLevel,
+    PackageSelection, ReleaseType, Report, Reporter, RequiredSemverUpdate, Rustdoc, SarifReporter,
+    ScopeSelection, SemverQuery, Violation,
+};
+
+use std::{collections::HashMap, time::Duration};
 
 #[test]
 fn major_required_bump_if_breaking_change() {
@@ -28,3 +34,672 @@ fn major_required_bump_if_breaking_change_and_major_bump_detected() {
     assert_eq!(required_bump, None);
     assert_eq!(crate_report.detected_bump(), ActualSemverUpdate::Major);
 }
+
+#[test]
+fn feature_flags_apply_to_both_versions() {
+    // Swapped, so that the current version no longer enables `unstable` by default.
+    let current = Rustdoc::from_root("test_crates/features_override/old/");
+    let baseline = Rustdoc::from_root("test_crates/features_override/new/");
+    let mut check = Check::new(current);
+    check.with_baseline(baseline);
+
+    let report = check.with_feature_flags(vec![]).check_release().unwrap();
+    assert!(!report.success());
+
+    let report = check
+        .with_feature_flags(vec!["unstable".to_string()])
+        .check_release()
+        .unwrap();
+    assert!(report.success());
+
+    let report = check.with_all_features().check_release().unwrap();
+    assert!(report.success());
+}
+
+#[test]
+fn default_features_can_be_disabled() {
+    // Only the baseline enables `unstable` by default.
+    let current = Rustdoc::from_root("test_crates/features_override/old/");
+    let baseline = Rustdoc::from_root("test_crates/features_override/new/");
+    let mut check = Check::new(current);
+    check.with_baseline(baseline);
+
+    let report = check.with_default_features(false).check_release().unwrap();
+    assert!(report.success());
+
+    let report = check.with_feature_flags(vec![]).check_release().unwrap();
+    assert!(report.success());
+
+    let report = check.with_default_features(true).check_release().unwrap();
+    assert!(!report.success());
+}
+
+#[test]
+fn features_override_applies_features_separately() {
+    let current = Rustdoc::from_root("test_crates/features_override/new/");
+    let baseline = Rustdoc::from_root("test_crates/features_override/old/");
+    let mut check = Check::new(current);
+    check.with_baseline(baseline);
+
+    // The baseline only exposes `stabilized_function()` with the `unstable` feature,
+    // while the current version enables that feature by default.
+    let mut features = FeatureOverride::new();
+    features
+        .with_baseline_features(vec!["unstable".to_string()])
+        .with_current_no_default(true);
+    let report = check
+        .with_features_override(features)
+        .check_release()
+        .unwrap();
+    assert!(!report.success());
+
+    let mut features = FeatureOverride::new();
+    features.with_baseline_features(vec!["unstable".to_string()]);
+    let report = check
+        .with_features_override(features)
+        .check_release()
+        .unwrap();
+    assert!(report.success());
+}
+
+#[test]
+fn metadata_is_recorded_in_report() {
+    let current = Rustdoc::from_root("test_crates/template/new/");
+    let baseline = Rustdoc::from_root("test_crates/template/old/");
+    let mut check = Check::new(current);
+    let report = check
+        .with_baseline(baseline)
+        .with_metadata("approved-by", "release-team")
+        .check_release()
+        .unwrap();
+    assert!(report.success());
+    assert_eq!(
+        report.metadata().get("approved-by").map(String::as_str),
+        Some("release-team")
+    );
+}
+
+#[derive(Default)]
+struct CollectingReporter {
+    started: Vec<String>,
+    violations: Vec<String>,
+    completed: usize,
+}
+
+impl Reporter for CollectingReporter {
+    fn on_package_start(
+        &mut self,
+        name: &str,
+        _version: Option<&semver::Version>,
+    ) -> anyhow::Result<()> {
+        self.started.push(name.to_string());
+        Ok(())
+    }
+
+    fn on_violation(&mut self, violation: &Violation) -> anyhow::Result<()> {
+        self.violations.push(violation.query_id().to_string());
+        Ok(())
+    }
+
+    fn on_package_complete(&mut self, _name: &str, _report: &CrateReport) -> anyhow::Result<()> {
+        self.completed += 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn reporter_receives_violations() {
+    let current = Rustdoc::from_root("test_crates/trait_missing/new/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    let check = check.with_baseline(baseline);
+
+    let mut reporter = CollectingReporter::default();
+    let report = check.check_release_with_reporter(&mut reporter).unwrap();
+    assert!(!report.success());
+
+    assert_eq!(reporter.started, ["trait_missing"]);
+    assert_eq!(reporter.completed, 1);
+    assert!(reporter.violations.iter().any(|id| id == "trait_missing"));
+
+    let (_crate_name, crate_report) = report.crate_reports().iter().next().unwrap();
+    let reported: Vec<_> = crate_report
+        .violations()
+        .iter()
+        .map(|violation| violation.query_id().to_string())
+        .collect();
+    assert_eq!(reported, reporter.violations);
+}
+
+#[test]
+fn annotation_pairs_describe_each_failed_lint() {
+    let current = Rustdoc::from_root("test_crates/trait_missing/new/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    let check = check.with_baseline(baseline);
+    let report = check.check_release().unwrap();
+
+    let (_crate_name, crate_report) = report.crate_reports().iter().next().unwrap();
+    let pairs = crate_report.as_annotation_pairs();
+    let (title, body) = pairs
+        .iter()
+        .find(|(title, _)| title.contains("(trait_missing)"))
+        .expect("no annotation for the trait_missing lint");
+    assert!(title.starts_with("semver violation in trait_missing:"));
+    assert!(body.contains("Failed in:"));
+    assert!(body.contains("RemovedTrait"));
+}
+
+#[test]
+fn assume_no_break_fails_despite_major_bump() {
+    let current = Rustdoc::from_root("test_crates/trait_missing_with_major_bump/old/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing_with_major_bump/new/");
+    let mut check = Check::new(current);
+    let check = check.with_baseline(baseline).with_assume_no_break();
+    let report = check.check_release().unwrap();
+    assert!(!report.success());
+    let (_crate_name, crate_report) = report.crate_reports().iter().next().unwrap();
+    assert_eq!(crate_report.required_bump(), Some(ReleaseType::Major));
+    assert_eq!(crate_report.detected_bump(), ActualSemverUpdate::Patch);
+}
+
+#[test]
+fn junit_reporter_writes_testsuite_per_crate() {
+    let current = Rustdoc::from_root("test_crates/trait_missing/new/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    let check = check.with_baseline(baseline);
+
+    let mut output = vec![];
+    let report = check
+        .check_release_with_reporter(JunitReporter::new(&mut output))
+        .unwrap();
+    assert!(!report.success());
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+    assert!(output.contains(r#"<testsuite name="trait_missing""#));
+    assert!(output.contains(r#"classname="trait_missing.trait_missing""#));
+    assert!(output.contains(r#"<failure type="major""#));
+    assert!(output.trim_end().ends_with("</testsuites>"));
+}
+
+#[test]
+fn sarif_reporter_writes_results_with_locations() {
+    let current = Rustdoc::from_root("test_crates/trait_missing/new/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    let check = check.with_baseline(baseline);
+
+    let mut output = vec![];
+    let report = check
+        .check_release_with_reporter(SarifReporter::new(&mut output))
+        .unwrap();
+    assert!(!report.success());
+
+    let sarif: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    let run = &sarif["runs"][0];
+    assert!(run["tool"]["driver"]["rules"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|rule| rule["id"] == "trait_missing"));
+    let result = run["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|result| result["ruleId"] == "trait_missing")
+        .expect("no trait_missing result");
+    assert_eq!(result["level"], "error");
+    let location = &result["locations"][0]["physicalLocation"];
+    assert_eq!(
+        location["artifactLocation"]["uri"],
+        "test_crates/trait_missing/old/src/lib.rs"
+    );
+    assert!(location["region"]["startLine"].is_u64());
+}
+
+#[test]
+fn reporters_fail_on_all_violations_in_pedantic_mode() {
+    let current = Rustdoc::from_root("test_crates/docs_changed/new/");
+    let baseline = Rustdoc::from_root("test_crates/docs_changed/old/");
+    let mut check = Check::new(current);
+    let check = check
+        .with_baseline(baseline)
+        .with_compare_docs()
+        .with_pedantic();
+
+    let mut output = vec![];
+    let report = check
+        .check_release_with_reporter(JunitReporter::new(&mut output))
+        .unwrap();
+    assert!(!report.success());
+    assert!(report.violations().all(Violation::fails_check));
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("<failure "), "{output}");
+    assert!(!output.contains(r#"failures="0""#), "{output}");
+    let sarif = report.to_sarif();
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert!(!results.is_empty());
+    assert!(results.iter().all(|result| result["level"] == "error"));
+}
+
+#[test]
+fn reporters_follow_lint_level_overrides() {
+    let current = Rustdoc::from_root("test_crates/trait_missing/new/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    let check = check
+        .with_baseline(baseline)
+        .with_lint_level("trait_missing", LintLevel::Warn)
+        .with_lint_level("trait_unsafe_added", LintLevel::Warn)
+        .with_lint_level("trait_unsafe_removed", LintLevel::Warn);
+
+    let mut output = vec![];
+    let report = check
+        .check_release_with_reporter(JunitReporter::new(&mut output))
+        .unwrap();
+    assert!(report.success());
+    assert!(!report.violations().any(Violation::fails_check));
+
+    let output = String::from_utf8(output).unwrap();
+    assert!(!output.contains("<failure "), "{output}");
+    assert!(output.contains(r#"failures="0">"#), "{output}");
+    let sarif = report.to_sarif();
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert!(!results.is_empty());
+    assert!(results.iter().all(|result| result["level"] == "warning"));
+}
+
+#[test]
+fn warn_level_violations_do_not_fail_check() {
+    let current = Rustdoc::from_root("test_crates/inline_attribute_changed/new/");
+    let baseline = Rustdoc::from_root("test_crates/inline_attribute_changed/old/");
+    let mut check = Check::new(current);
+    let check = check.with_baseline(baseline);
+    let report = check.check_release().unwrap();
+    assert!(report.success());
+
+    let (_crate_name, crate_report) = report.crate_reports().iter().next().unwrap();
+    assert_eq!(crate_report.required_bump(), None);
+    assert!(crate_report
+        .violations()
+        .iter()
+        .any(
+            |violation| violation.query_id() == "inline_attribute_changed"
+                && violation.lint_level() == LintLevel::Warn
+        ));
+}
+
+#[test]
+fn docs_changes_are_only_reported_on_request() {
+    let current = Rustdoc::from_root("test_crates/docs_changed/new/");
+    let baseline = Rustdoc::from_root("test_crates/docs_changed/old/");
+    let mut check = Check::new(current);
+    let check = check.with_baseline(baseline);
+    let report = check.check_release().unwrap();
+    let (_crate_name, crate_report) = report.crate_reports().iter().next().unwrap();
+    assert!(crate_report.violations().is_empty());
+
+    let report = check.with_compare_docs().check_release().unwrap();
+    assert!(report.success());
+    let (_crate_name, crate_report) = report.crate_reports().iter().next().unwrap();
+    assert!(crate_report
+        .violations()
+        .iter()
+        .all(|violation| violation.lint_level() == LintLevel::Info));
+    assert_eq!(crate_report.violations().len(), 5);
+}
+
+#[test]
+fn pedantic_fails_on_informational_violations() {
+    let current = Rustdoc::from_root("test_crates/docs_changed/new/");
+    let baseline = Rustdoc::from_root("test_crates/docs_changed/old/");
+    let mut check = Check::new(current);
+    let check = check.with_baseline(baseline).with_pedantic();
+    let report = check.check_release().unwrap();
+    assert!(report.success());
+
+    let report = check.with_compare_docs().check_release().unwrap();
+    assert!(!report.success());
+}
+
+#[test]
+fn lint_level_overrides() {
+    let current = Rustdoc::from_root("test_crates/trait_missing/new/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    let check = check.with_baseline(baseline);
+    let violations = |report: &Report| -> Vec<LintLevel> {
+        report
+            .violations()
+            .filter(|violation| violation.query_id() == "trait_missing")
+            .map(|violation| violation.lint_level())
+            .collect()
+    };
+
+    let report = check
+        .with_lint_level("trait_missing", LintLevel::Warn)
+        .check_release()
+        .unwrap();
+    assert!(!violations(&report).is_empty());
+    assert!(violations(&report)
+        .iter()
+        .all(|level| *level == LintLevel::Warn));
+
+    let report = check
+        .with_lint_level("trait_missing", LintLevel::Allow)
+        .check_release()
+        .unwrap();
+    assert!(violations(&report).is_empty());
+
+    let err = check
+        .with_lint_level("no_such_lint", LintLevel::Deny)
+        .check_release()
+        .unwrap_err();
+    assert!(format!("{err:#}").contains("no_such_lint"), "{err:#}");
+}
+
+#[test]
+fn package_selection_manifest_path_names_rustdoc_files() {
+    let current = Rustdoc::from_path("localdata/test_data/trait_missing/new/rustdoc.json");
+    let baseline = Rustdoc::from_path("localdata/test_data/trait_missing/old/rustdoc.json");
+    let mut selection = PackageSelection::new(ScopeSelection::DefaultMembers);
+    selection.with_manifest_path("test_crates/trait_missing/new/Cargo.toml".into());
+    let mut check = Check::new(current);
+    let report = check
+        .with_baseline(baseline)
+        .with_package_selection(selection)
+        .check_release()
+        .unwrap();
+
+    let crate_names: Vec<_> = report.crate_reports().keys().collect();
+    assert_eq!(crate_names, ["trait_missing"]);
+}
+
+#[test]
+fn violations_carry_suggested_fix() {
+    let current = Rustdoc::from_root("test_crates/trait_missing/new/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    let check = check.with_baseline(baseline);
+    let report = check.check_release().unwrap();
+
+    let (_crate_name, crate_report) = report.crate_reports().iter().next().unwrap();
+    let violation = crate_report
+        .violations()
+        .iter()
+        .find(|violation| violation.query_id() == "trait_missing")
+        .expect("no trait_missing violation");
+    assert!(violation
+        .suggested_fix()
+        .expect("trait_missing has a suggested fix")
+        .contains("pub use"));
+}
+
+#[test]
+fn warnings_only_lints_do_not_fail_check() {
+    let current = Rustdoc::from_root("test_crates/trait_missing/new/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    check.with_baseline(baseline);
+    assert!(!check.check_release().unwrap().success());
+
+    check.with_warnings_only(vec![
+        "trait_missing".to_string(),
+        "trait_unsafe_added".to_string(),
+        "trait_unsafe_removed".to_string(),
+    ]);
+    assert!(check.check_release().unwrap().success());
+
+    check.with_warnings_only(vec!["not_a_real_lint".to_string()]);
+    assert!(check.check_release().is_err());
+}
+
+#[test]
+fn environment_is_passed_to_cargo() {
+    let current = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    check.with_baseline(baseline);
+    assert!(check.check_release().unwrap().success());
+
+    let environment = HashMap::from([(
+        "RUSTDOCFLAGS".to_string(),
+        "--not-a-real-rustdoc-flag".to_string(),
+    )]);
+    assert!(check.with_environment(environment).check_release().is_err());
+}
+
+#[test]
+fn intercepted_cargo_stderr_is_included_in_error() {
+    let current = Rustdoc::from_root("test_crates/trait_missing/new/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    check
+        .with_baseline(baseline)
+        .with_environment(HashMap::from([(
+            "RUSTDOCFLAGS".to_string(),
+            "--not-a-real-rustdoc-flag".to_string(),
+        )]));
+
+    let err = check
+        .with_cargo_stderr_intercepted(true)
+        .check_release()
+        .unwrap_err();
+    let message = format!("{:#}", anyhow::Error::from(err));
+    assert!(message.contains("not-a-real-rustdoc-flag"), "{message}");
+
+    let err = check
+        .with_cargo_stderr_intercepted(false)
+        .check_release()
+        .unwrap_err();
+    let message = format!("{:#}", anyhow::Error::from(err));
+    assert!(!message.contains("not-a-real-rustdoc-flag"), "{message}");
+    assert!(message.contains("See stderr."), "{message}");
+}
+
+#[test]
+fn try_check_release_skips_unchanged_crates() {
+    let current = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    check.with_baseline(baseline);
+    assert!(check.try_check_release().unwrap().is_none());
+
+    let current = Rustdoc::from_root("test_crates/trait_missing/new/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    check.with_baseline(baseline);
+    let report = check.try_check_release().unwrap().unwrap();
+    assert!(!report.success());
+}
+
+#[test]
+fn check_release_with_timeout() {
+    let current = Rustdoc::from_root("test_crates/trait_missing/new/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    check.with_baseline(baseline);
+
+    assert!(matches!(
+        check.check_release_with_timeout(Duration::ZERO),
+        Err(CheckError::Timeout { .. })
+    ));
+
+    let report = check
+        .check_release_with_timeout(Duration::from_secs(600))
+        .unwrap();
+    assert!(!report.success());
+}
+
+#[test]
+fn check_release_in_ci() {
+    let current = Rustdoc::from_root("test_crates/trait_missing/new/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    check.with_baseline(baseline);
+
+    // Whether or not a CI environment is detected, the results are the same.
+    let report = check.check_release_in_ci().unwrap();
+    assert!(!report.success());
+}
+
+#[test]
+fn check_release_with_ci_defaults_writes_json_report() {
+    let current = Rustdoc::from_root("test_crates/trait_unsafe_added/new/");
+    let baseline = Rustdoc::from_root("test_crates/trait_unsafe_added/old/");
+    let mut check = Check::new(current);
+    check.with_baseline(baseline);
+
+    let path = check.ci_report_path().unwrap();
+    assert!(path.starts_with(std::fs::canonicalize("test_crates/trait_unsafe_added/new").unwrap()));
+    let _ = std::fs::remove_file(&path);
+
+    let report = check.check_release_with_ci_defaults().unwrap();
+    assert!(!report.success());
+    let json: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(&path).expect("no JSON report written")).unwrap();
+    assert_eq!(json, report.to_json());
+}
+
+#[test]
+fn migration_guide_lists_breaking_changes() {
+    let current = Rustdoc::from_root("test_crates/trait_missing/new/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    let report = check.with_baseline(baseline).check_release().unwrap();
+
+    let mut guide = Vec::new();
+    report.write_migration_guide(&mut guide).unwrap();
+    let guide = String::from_utf8(guide).unwrap();
+    assert!(guide.starts_with("# Migration guide\n"));
+    assert!(guide.contains("## `trait_missing`"));
+    assert!(guide.contains("RemovedTrait"));
+    assert!(guide.contains("**How to update:** TODO"));
+}
+
+#[test]
+fn report_to_json() {
+    let current = Rustdoc::from_root("test_crates/trait_missing/new/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    let report = check.with_baseline(baseline).check_release().unwrap();
+
+    let json = report.to_json();
+    assert_eq!(json["success"], false);
+    let crate_report = &json["crates"]["trait_missing"];
+    assert_eq!(crate_report["required_bump"], "major");
+    let violation = crate_report["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|violation| {
+            violation["query_id"] == "trait_missing"
+                && violation["values"]["name"] == "RemovedTrait"
+        })
+        .expect("no trait_missing violation");
+    assert_eq!(violation["lint_level"], "deny");
+    assert_eq!(violation["required_update"], "major");
+    assert!(violation["span"]["filename"]
+        .as_str()
+        .unwrap()
+        .ends_with("src/lib.rs"));
+    assert_eq!(violation["span"]["begin_line"], 1);
+}
+
+#[test]
+fn report_exposes_violations() {
+    let current = Rustdoc::from_root("test_crates/trait_missing/new/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    let report = check.with_baseline(baseline).check_release().unwrap();
+
+    let violation = report
+        .violations()
+        .find(|violation| violation.item_path().as_deref() == Some("trait_missing::RemovedTrait"))
+        .expect("no violation for RemovedTrait");
+    assert_eq!(violation.query_id(), "trait_missing");
+    assert_eq!(violation.crate_name(), "trait_missing");
+    assert_eq!(violation.required_update(), RequiredSemverUpdate::Major);
+}
+
+#[test]
+fn query_metadata_is_public() {
+    let queries = SemverQuery::all_queries();
+    let query = &queries["function_missing"];
+    assert_eq!(query.id, "function_missing");
+    assert!(!query.human_readable_name.is_empty());
+    assert!(!query.description.is_empty());
+    assert!(!query.error_message.is_empty());
+    assert_eq!(query.required_update, RequiredSemverUpdate::Major);
+    assert_eq!(query.lint_level, LintLevel::Deny);
+}
+
+#[test]
+fn semver_exempt_items_are_not_reported() {
+    let current = Rustdoc::from_root("test_crates/semver_exempt/new/");
+    let baseline = Rustdoc::from_root("test_crates/semver_exempt/old/");
+    let mut check = Check::new(current);
+    let report = check.with_baseline(baseline).check_release().unwrap();
+
+    let mut reported: Vec<_> = report
+        .violations()
+        .filter_map(|violation| violation.item_path())
+        .collect();
+    reported.sort();
+    assert_eq!(
+        reported,
+        [
+            "semver_exempt::RegularStruct",
+            "semver_exempt::regular_function"
+        ]
+    );
+}
+
+#[test]
+fn report_to_sarif() {
+    let current = Rustdoc::from_root("test_crates/trait_missing/new/");
+    let baseline = Rustdoc::from_root("test_crates/trait_missing/old/");
+    let mut check = Check::new(current);
+    let report = check.with_baseline(baseline).check_release().unwrap();
+
+    let sarif = report.to_sarif();
+    assert_eq!(sarif["version"], "2.1.0");
+    let results = sarif["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), report.violations().count());
+}
+
+#[test]
+fn remote_url_rustdoc() {
+    let current = std::fs::canonicalize("localdata/test_data/trait_missing/new/rustdoc.json")
+        .expect("missing test rustdoc");
+    let current = Rustdoc::from_remote_url(format!("file://{}", current.display()));
+    let baseline = Rustdoc::from_path("localdata/test_data/trait_missing/old/rustdoc.json");
+    let mut check = Check::new(current);
+    let report = check.with_baseline(baseline).check_release().unwrap();
+    assert!(!report.success());
+
+    let missing = Rustdoc::from_remote_url("file:///nonexistent/rustdoc.json");
+    let baseline = Rustdoc::from_path("localdata/test_data/trait_missing/old/rustdoc.json");
+    let mut check = Check::new(missing);
+    let err = check.with_baseline(baseline).check_release().unwrap_err();
+    let CheckError::Rustdoc(source) = err else {
+        panic!("expected a rustdoc error, got {err:?}");
+    };
+    assert!(
+        source.to_string().contains("failed to download rustdoc"),
+        "{source:#}"
+    );
+}
+
+#[test]
+fn check_error_baseline() {
+    let current = Rustdoc::from_path("localdata/test_data/trait_missing/new/rustdoc.json");
+    let baseline = Rustdoc::from_path("localdata/test_data/nonexistent/old/rustdoc.json");
+    let mut check = Check::new(current);
+    let err = check.with_baseline(baseline).check_release().unwrap_err();
+    assert!(matches!(err, CheckError::Baseline(_)), "{err:?}");
+}